@@ -0,0 +1,87 @@
+//! Compares `SpatialHash::query_region` with a linear scan over 50k entities.
+//!
+//! Run with `cargo bench --bench spatial`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use engine::collision::Aabb;
+use engine::spatial::SpatialHash;
+
+const ENTITIES: u32 = 50_000;
+const QUERIES: usize = 1_000;
+const WORLD_SIZE: f32 = 5_000.0;
+const QUERY_SIZE: f32 = 200.0;
+
+/// Xorshift, so every run measures the same scene.
+struct Rng(u32);
+
+impl Rng {
+    fn next_f32(&mut self) -> f32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 17;
+        self.0 ^= self.0 << 5;
+        (self.0 >> 8) as f32 / (1u32 << 24) as f32
+    }
+
+    fn range(&mut self, min: f32, max: f32) -> f32 {
+        min + (max - min) * self.next_f32()
+    }
+}
+
+fn random_box(rng: &mut Rng, min_size: f32, max_size: f32) -> Aabb {
+    let size = rng.range(min_size, max_size);
+    let position = [rng.range(0.0, WORLD_SIZE), rng.range(0.0, WORLD_SIZE)];
+    Aabb::from_position_size(position, [size, size])
+}
+
+fn time(name: &str, mut run: impl FnMut() -> usize) -> Duration {
+    let start = Instant::now();
+    let hits = black_box(run());
+    let elapsed = start.elapsed();
+    println!(
+        "{name:<12} {:>10.3?} total, {:>8.2?} per query, {hits} hits",
+        elapsed,
+        elapsed / QUERIES as u32
+    );
+    elapsed
+}
+
+fn main() {
+    let mut rng = Rng(0x2545_f491);
+    let entities: Vec<(u32, Aabb)> = (0..ENTITIES)
+        .map(|id| (id, random_box(&mut rng, 5.0, 15.0)))
+        .collect();
+    let queries: Vec<Aabb> = (0..QUERIES)
+        .map(|_| random_box(&mut rng, QUERY_SIZE, QUERY_SIZE))
+        .collect();
+
+    let build = Instant::now();
+    let mut hash = SpatialHash::for_average_size(10.0);
+    for &(id, bounds) in &entities {
+        hash.insert(id, bounds);
+    }
+    println!("{ENTITIES} entities indexed in {:.3?}", build.elapsed());
+
+    let linear = time("linear scan", || {
+        queries
+            .iter()
+            .map(|region| {
+                entities
+                    .iter()
+                    .filter(|(_, bounds)| bounds.intersects(region))
+                    .count()
+            })
+            .sum()
+    });
+    let indexed = time("spatial hash", || {
+        queries
+            .iter()
+            .map(|region| hash.query_region(region).len())
+            .sum()
+    });
+    println!(
+        "speedup: {:.1}x",
+        linear.as_secs_f64() / indexed.as_secs_f64()
+    );
+}
@@ -0,0 +1,272 @@
+/// What an [`Animation`] does when it runs past its last frame.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PlayMode {
+    /// Wrap back to the first frame.
+    #[default]
+    Loop,
+    /// Reverse direction at either end.
+    PingPong,
+    /// Stop on the last frame.
+    Once,
+}
+
+/// How [`Animation::switch_to`] picks the frame to continue from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transition {
+    /// Start the new animation from its first frame.
+    Reset,
+    /// Keep the current frame index and time into it, so e.g. walk and run cycles of the same
+    /// length switch without popping.
+    Continue,
+}
+
+/// Steps through a list of frames (texture sub-rects as `[x, y, w, h]`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Animation {
+    pub frames: Vec<[f32; 4]>,
+    /// Seconds each frame is shown for, unless overridden by `durations`.
+    pub frame_time: f32,
+    /// Per-frame display times in seconds. Empty means every frame uses `frame_time`.
+    pub durations: Vec<f32>,
+    pub elapsed: f32,
+    pub current: usize,
+    pub mode: PlayMode,
+    /// Playback rate multiplier, 1 is normal speed.
+    pub speed: f32,
+    pub paused: bool,
+    reversing: bool,
+    finished: bool,
+}
+
+impl Animation {
+    pub fn new(frames: Vec<[f32; 4]>, frame_time: f32) -> Self {
+        assert!(!frames.is_empty(), "animation needs at least one frame");
+        Self {
+            frames,
+            frame_time,
+            durations: Vec::new(),
+            elapsed: 0.0,
+            current: 0,
+            mode: PlayMode::Loop,
+            speed: 1.0,
+            paused: false,
+            reversing: false,
+            finished: false,
+        }
+    }
+
+    /// An animation that stops on its last frame instead of wrapping.
+    pub fn once(frames: Vec<[f32; 4]>, frame_time: f32) -> Self {
+        Self::new(frames, frame_time).with_mode(PlayMode::Once)
+    }
+
+    /// An animation where each frame has its own display time.
+    pub fn with_durations(frames: Vec<[f32; 4]>, durations: Vec<f32>) -> Self {
+        assert_eq!(
+            frames.len(),
+            durations.len(),
+            "every frame needs a duration"
+        );
+        Self {
+            durations,
+            ..Self::new(frames, 0.0)
+        }
+    }
+
+    pub fn with_mode(mut self, mode: PlayMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Seconds frame `index` is shown for.
+    pub fn duration(&self, index: usize) -> f32 {
+        self.durations
+            .get(index)
+            .copied()
+            .unwrap_or(self.frame_time)
+    }
+
+    /// Advances the animation by `dt` seconds, skipping as many frames as that covers.
+    ///
+    /// Frames with a zero (or negative) duration are skipped over without being shown.
+    pub fn tick(&mut self, dt: f32) {
+        if self.paused || self.finished {
+            return;
+        }
+        self.elapsed += dt * self.speed;
+        // A ping-pong cycle visits each frame at most twice, so more zero-length frames in a
+        // row than that means every frame is zero-length.
+        let max_skips = 2 * self.frames.len();
+        let mut skipped = 0;
+        while self.elapsed >= self.duration(self.current) {
+            let duration = self.duration(self.current);
+            if duration <= 0.0 {
+                skipped += 1;
+                if skipped > max_skips {
+                    self.elapsed = 0.0;
+                    break;
+                }
+            } else {
+                skipped = 0;
+                self.elapsed -= duration;
+            }
+            self.advance_frame();
+            if self.finished {
+                self.elapsed = 0.0;
+                break;
+            }
+        }
+    }
+
+    fn advance_frame(&mut self) {
+        let last = self.frames.len() - 1;
+        match self.mode {
+            PlayMode::Loop => {
+                self.current = if self.current < last {
+                    self.current + 1
+                } else {
+                    0
+                }
+            }
+            PlayMode::Once => {
+                if self.current < last {
+                    self.current += 1;
+                } else {
+                    self.finished = true;
+                }
+            }
+            PlayMode::PingPong => {
+                if last == 0 {
+                    return;
+                }
+                if self.reversing && self.current == 0 {
+                    self.reversing = false;
+                } else if !self.reversing && self.current == last {
+                    self.reversing = true;
+                }
+                if self.reversing {
+                    self.current -= 1;
+                } else {
+                    self.current += 1;
+                }
+            }
+        }
+    }
+
+    pub fn current_frame(&self) -> [f32; 4] {
+        self.frames[self.current]
+    }
+
+    /// Whether a [`PlayMode::Once`] animation has reached and finished showing its last frame.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Starts over from the first frame.
+    pub fn reset(&mut self) {
+        self.current = 0;
+        self.elapsed = 0.0;
+        self.reversing = false;
+        self.finished = false;
+    }
+
+    /// Replaces the frames and timing with those of `next`, keeping speed and pause state.
+    pub fn switch_to(&mut self, next: Animation, transition: Transition) {
+        let (current, elapsed) = (self.current, self.elapsed);
+        *self = Animation {
+            speed: self.speed,
+            paused: self.paused,
+            ..next
+        };
+        self.reset();
+        if transition == Transition::Continue {
+            self.current = current.min(self.frames.len() - 1);
+            self.elapsed = elapsed;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frames(n: usize) -> Vec<[f32; 4]> {
+        (0..n).map(|i| [i as f32, 0.0, 1.0, 1.0]).collect()
+    }
+
+    #[test]
+    fn fractional_ticks_accumulate_and_wrap() {
+        let mut anim = Animation::new(frames(3), 0.1);
+        anim.tick(0.06);
+        assert_eq!(anim.current, 0);
+        anim.tick(0.06);
+        assert_eq!(anim.current, 1);
+        anim.tick(0.25);
+        assert_eq!(anim.current, 0);
+        assert_eq!(anim.current_frame(), [0.0, 0.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn once_clamps_on_last_frame() {
+        let mut anim = Animation::once(frames(3), 0.1);
+        anim.tick(0.25);
+        assert_eq!(anim.current, 2);
+        assert!(!anim.is_finished());
+        anim.tick(0.5);
+        assert_eq!(anim.current, 2);
+        assert!(anim.is_finished());
+        anim.reset();
+        assert_eq!(anim.current, 0);
+        assert!(!anim.is_finished());
+    }
+
+    #[test]
+    fn ping_pong_reverses_at_both_ends() {
+        let mut anim = Animation::new(frames(3), 0.25).with_mode(PlayMode::PingPong);
+        let mut visited = vec![anim.current];
+        for _ in 0..5 {
+            anim.tick(0.25);
+            visited.push(anim.current);
+        }
+        assert_eq!(visited, vec![0, 1, 2, 1, 0, 1]);
+    }
+
+    #[test]
+    fn speed_scales_and_pause_holds_playback() {
+        let mut anim = Animation::new(frames(4), 0.25);
+        anim.speed = 2.0;
+        anim.tick(0.25);
+        assert_eq!(anim.current, 2);
+        anim.paused = true;
+        anim.tick(1.0);
+        assert_eq!(anim.current, 2);
+    }
+
+    #[test]
+    fn continue_transition_clamps_to_shorter_animation() {
+        let mut anim = Animation::new(frames(4), 0.25);
+        anim.speed = 0.5;
+        anim.tick(1.6);
+        assert_eq!(anim.current, 3);
+        anim.switch_to(Animation::new(frames(2), 0.25), Transition::Continue);
+        assert_eq!(anim.current, 1);
+        assert!((anim.elapsed - 0.05).abs() < 1e-5);
+        assert_eq!(anim.speed, 0.5);
+        anim.switch_to(Animation::new(frames(3), 0.25), Transition::Reset);
+        assert_eq!(anim.current, 0);
+        assert_eq!(anim.elapsed, 0.0);
+    }
+
+    #[test]
+    fn zero_length_frames_are_skipped() {
+        let mut anim = Animation::with_durations(frames(3), vec![0.25, 0.0, 0.25]);
+        anim.tick(0.25);
+        assert_eq!(anim.current, 2);
+        anim.tick(0.25);
+        assert_eq!(anim.current, 0);
+
+        let mut frozen = Animation::with_durations(frames(3), vec![0.0; 3]);
+        frozen.tick(1.0);
+        assert_eq!(frozen.elapsed, 0.0);
+    }
+}
@@ -0,0 +1,215 @@
+use crate::transform::{transform_point, Transform2D};
+
+/// An axis-aligned bounding box in world units.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Aabb {
+    pub min: [f32; 2],
+    pub max: [f32; 2],
+}
+
+impl Aabb {
+    pub fn new(min: [f32; 2], max: [f32; 2]) -> Self {
+        Self { min, max }
+    }
+
+    /// A box with its bottom-left corner at `position`.
+    pub fn from_position_size(position: [f32; 2], size: [f32; 2]) -> Self {
+        Self {
+            min: position,
+            max: [position[0] + size[0], position[1] + size[1]],
+        }
+    }
+
+    /// A box centred on `center`.
+    pub fn from_center_half_extents(center: [f32; 2], half_extents: [f32; 2]) -> Self {
+        Self {
+            min: [center[0] - half_extents[0], center[1] - half_extents[1]],
+            max: [center[0] + half_extents[0], center[1] + half_extents[1]],
+        }
+    }
+
+    /// The smallest box containing every point. Returns `None` for an empty slice.
+    pub fn from_points(points: &[[f32; 2]]) -> Option<Self> {
+        let (first, rest) = points.split_first()?;
+        let mut aabb = Self::new(*first, *first);
+        for p in rest {
+            aabb.min = [aabb.min[0].min(p[0]), aabb.min[1].min(p[1])];
+            aabb.max = [aabb.max[0].max(p[0]), aabb.max[1].max(p[1])];
+        }
+        Some(aabb)
+    }
+
+    /// The box enclosing a unit quad centred on the origin after `transform`, so rotated quads
+    /// get the box around all four corners.
+    pub fn from_transform(transform: &Transform2D) -> Self {
+        let m = transform.to_matrix();
+        let corners =
+            [[-0.5, -0.5], [0.5, -0.5], [0.5, 0.5], [-0.5, 0.5]].map(|c| transform_point(&m, c));
+        Self::from_points(&corners).expect("four corners")
+    }
+
+    pub fn width(&self) -> f32 {
+        self.max[0] - self.min[0]
+    }
+
+    pub fn height(&self) -> f32 {
+        self.max[1] - self.min[1]
+    }
+
+    pub fn center(&self) -> [f32; 2] {
+        [
+            (self.min[0] + self.max[0]) * 0.5,
+            (self.min[1] + self.max[1]) * 0.5,
+        ]
+    }
+
+    /// Whether the boxes overlap. Boxes that only share an edge count as intersecting.
+    pub fn intersects(&self, other: &Aabb) -> bool {
+        self.min[0] <= other.max[0]
+            && self.max[0] >= other.min[0]
+            && self.min[1] <= other.max[1]
+            && self.max[1] >= other.min[1]
+    }
+
+    /// Whether `p` lies inside the box or on its edge.
+    pub fn contains_point(&self, p: [f32; 2]) -> bool {
+        p[0] >= self.min[0] && p[0] <= self.max[0] && p[1] >= self.min[1] && p[1] <= self.max[1]
+    }
+}
+
+/// Where a moving box first touches another, from [`Aabb::sweep`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SweepHit {
+    /// Fraction of the movement, in 0..=1, at which the boxes first touch.
+    pub time: f32,
+    /// Surface normal of the struck face, pointing back towards the moving box. Zero when the
+    /// boxes were already overlapping.
+    pub normal: [f32; 2],
+}
+
+impl Aabb {
+    /// Moves `self` by `velocity` (the full displacement for this step) and reports when it
+    /// first hits `other`, if it does.
+    ///
+    /// Boxes that only slide along each other's edges or graze a corner do not count as a hit,
+    /// boxes that already overlap hit at time 0.
+    pub fn sweep(&self, velocity: [f32; 2], other: &Aabb) -> Option<SweepHit> {
+        if self.overlaps_strictly(other) {
+            return Some(SweepHit {
+                time: 0.0,
+                normal: [0.0, 0.0],
+            });
+        }
+        let mut entry = [f32::NEG_INFINITY; 2];
+        let mut exit = [f32::INFINITY; 2];
+        for axis in 0..2 {
+            let v = velocity[axis];
+            if v > 0.0 {
+                entry[axis] = (other.min[axis] - self.max[axis]) / v;
+                exit[axis] = (other.max[axis] - self.min[axis]) / v;
+            } else if v < 0.0 {
+                entry[axis] = (other.max[axis] - self.min[axis]) / v;
+                exit[axis] = (other.min[axis] - self.max[axis]) / v;
+            } else if self.max[axis] <= other.min[axis] || self.min[axis] >= other.max[axis] {
+                // Not moving on this axis and not overlapping on it, so the boxes never meet.
+                return None;
+            }
+        }
+        let time = entry[0].max(entry[1]);
+        let leave = exit[0].min(exit[1]);
+        if time >= leave || !(0.0..=1.0).contains(&time) {
+            return None;
+        }
+        let axis = if entry[0] >= entry[1] { 0 } else { 1 };
+        let mut normal = [0.0; 2];
+        normal[axis] = -velocity[axis].signum();
+        Some(SweepHit { time, normal })
+    }
+
+    /// Overlap with a positive area, touching edges excluded.
+    fn overlaps_strictly(&self, other: &Aabb) -> bool {
+        self.min[0] < other.max[0]
+            && self.max[0] > other.min[0]
+            && self.min[1] < other.max[1]
+            && self.max[1] > other.min[1]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unit() -> Aabb {
+        Aabb::new([0.0, 0.0], [1.0, 1.0])
+    }
+
+    #[test]
+    fn touching_edges_intersect() {
+        let right = Aabb::new([1.0, 0.0], [2.0, 1.0]);
+        assert!(unit().intersects(&right));
+        assert!(right.intersects(&unit()));
+        assert!(unit().contains_point([1.0, 0.5]));
+    }
+
+    #[test]
+    fn contained_box_intersects() {
+        let outer = Aabb::from_center_half_extents([0.5, 0.5], [2.0, 2.0]);
+        let inner = Aabb::from_position_size([0.25, 0.25], [0.5, 0.5]);
+        assert!(outer.intersects(&inner));
+        assert!(inner.intersects(&outer));
+        assert!(inner.contains_point(inner.center()));
+    }
+
+    #[test]
+    fn disjoint_boxes_do_not_intersect() {
+        let far = Aabb::new([1.5, 1.5], [2.0, 2.0]);
+        assert!(!unit().intersects(&far));
+        assert!(!unit().contains_point([1.2, 0.5]));
+    }
+
+    #[test]
+    fn sweep_hits_face_with_normal() {
+        let floor = Aabb::new([0.0, -3.0], [1.0, -0.5]);
+        let hit = unit().sweep([0.0, -2.0], &floor).unwrap();
+        assert_eq!(hit.time, 0.25);
+        assert_eq!(hit.normal, [0.0, 1.0]);
+    }
+
+    #[test]
+    fn sweep_ignores_touching_on_a_still_axis() {
+        let above_right = Aabb::new([2.0, 1.0], [3.0, 2.0]);
+        assert_eq!(unit().sweep([5.0, 0.0], &above_right), None);
+    }
+
+    #[test]
+    fn sweep_ignores_diagonal_corner_graze() {
+        let corner = Aabb::new([-1.0, 2.0], [1.0, 3.0]);
+        assert_eq!(unit().sweep([2.0, 2.0], &corner), None);
+    }
+
+    #[test]
+    fn sweep_from_touching_depends_on_direction() {
+        let right = Aabb::new([1.0, 0.0], [2.0, 1.0]);
+        let hit = unit().sweep([1.0, 0.0], &right).unwrap();
+        assert_eq!(hit.time, 0.0);
+        assert_eq!(hit.normal, [-1.0, 0.0]);
+        assert_eq!(unit().sweep([-1.0, 0.0], &right), None);
+    }
+
+    #[test]
+    fn sweep_reaches_contact_at_end_of_step() {
+        let ahead = Aabb::new([2.0, 0.0], [3.0, 1.0]);
+        let hit = unit().sweep([1.0, 0.0], &ahead).unwrap();
+        assert_eq!(hit.time, 1.0);
+        assert_eq!(hit.normal, [-1.0, 0.0]);
+        assert_eq!(unit().sweep([0.5, 0.0], &ahead), None);
+    }
+
+    #[test]
+    fn sweep_starting_inside_hits_immediately() {
+        let overlapping = Aabb::new([0.5, 0.5], [1.5, 1.5]);
+        let hit = unit().sweep([1.0, 0.0], &overlapping).unwrap();
+        assert_eq!(hit.time, 0.0);
+        assert_eq!(hit.normal, [0.0, 0.0]);
+    }
+}
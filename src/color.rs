@@ -0,0 +1,250 @@
+use std::fmt;
+
+/// An RGBA colour with components in 0..1, in sRGB space.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Color {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+impl Color {
+    pub const WHITE: Color = Color::rgb(1.0, 1.0, 1.0);
+    pub const BLACK: Color = Color::rgb(0.0, 0.0, 0.0);
+    pub const RED: Color = Color::rgb(1.0, 0.0, 0.0);
+    pub const GREEN: Color = Color::rgb(0.0, 1.0, 0.0);
+    pub const BLUE: Color = Color::rgb(0.0, 0.0, 1.0);
+    pub const YELLOW: Color = Color::rgb(1.0, 1.0, 0.0);
+    pub const CYAN: Color = Color::rgb(0.0, 1.0, 1.0);
+    pub const MAGENTA: Color = Color::rgb(1.0, 0.0, 1.0);
+    pub const TRANSPARENT: Color = Color::rgba(0.0, 0.0, 0.0, 0.0);
+
+    pub const fn rgb(r: f32, g: f32, b: f32) -> Self {
+        Self::rgba(r, g, b, 1.0)
+    }
+
+    pub const fn rgba(r: f32, g: f32, b: f32, a: f32) -> Self {
+        Self { r, g, b, a }
+    }
+
+    /// A colour from 0..255 components.
+    pub fn rgb8(r: u8, g: u8, b: u8) -> Self {
+        Self::rgba8(r, g, b, 255)
+    }
+
+    /// A colour from 0..255 components.
+    pub fn rgba8(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self::rgba(
+            r as f32 / 255.0,
+            g as f32 / 255.0,
+            b as f32 / 255.0,
+            a as f32 / 255.0,
+        )
+    }
+
+    /// A colour from hue in degrees (wrapping), and saturation and value in 0..1.
+    pub fn hsv(h: f32, s: f32, v: f32) -> Self {
+        let h = h.rem_euclid(360.0) / 60.0;
+        let c = v * s;
+        let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+        let (r, g, b) = match h as u32 {
+            0 => (c, x, 0.0),
+            1 => (x, c, 0.0),
+            2 => (0.0, c, x),
+            3 => (0.0, x, c),
+            4 => (x, 0.0, c),
+            _ => (c, 0.0, x),
+        };
+        let m = v - c;
+        Self::rgb(r + m, g + m, b + m)
+    }
+
+    /// Parses `#rgb`, `#rrggbb` or `#rrggbbaa`. The leading `#` is optional.
+    pub fn from_hex(hex: &str) -> Result<Self, ParseColorError> {
+        let digits = hex.strip_prefix('#').unwrap_or(hex);
+        if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(ParseColorError::InvalidDigit);
+        }
+        let channel = |i: usize, width: usize| {
+            let value = u8::from_str_radix(&digits[i * width..(i + 1) * width], 16)
+                .map_err(|_| ParseColorError::InvalidDigit)?;
+            // A single digit stands for itself repeated, `f` is `ff`.
+            let value = if width == 1 { value * 0x11 } else { value };
+            Ok(value as f32 / 255.0)
+        };
+        match digits.len() {
+            3 => Ok(Self::rgb(channel(0, 1)?, channel(1, 1)?, channel(2, 1)?)),
+            6 => Ok(Self::rgb(channel(0, 2)?, channel(1, 2)?, channel(2, 2)?)),
+            8 => Ok(Self::rgba(
+                channel(0, 2)?,
+                channel(1, 2)?,
+                channel(2, 2)?,
+                channel(3, 2)?,
+            )),
+            len => Err(ParseColorError::InvalidLength(len)),
+        }
+    }
+
+    /// Returns the same colour with a different alpha.
+    pub fn with_alpha(self, a: f32) -> Self {
+        Self { a, ..self }
+    }
+
+    /// Interpolates component-wise, `t = 0` gives `self` and `t = 1` gives `other`.
+    pub fn lerp(self, other: Color, t: f32) -> Self {
+        let mix = |a: f32, b: f32| a + (b - a) * t;
+        Self::rgba(
+            mix(self.r, other.r),
+            mix(self.g, other.g),
+            mix(self.b, other.b),
+            mix(self.a, other.a),
+        )
+    }
+
+    /// Converts the colour channels from sRGB to linear space, leaving alpha as is.
+    pub fn to_linear(self) -> [f32; 4] {
+        [
+            srgb_to_linear(self.r),
+            srgb_to_linear(self.g),
+            srgb_to_linear(self.b),
+            self.a,
+        ]
+    }
+
+    /// The components to upload to the GPU, given which space the colour was written in.
+    pub fn to_gpu(self, space: ColorSpace) -> [f32; 4] {
+        match space {
+            ColorSpace::Srgb => self.to_linear(),
+            ColorSpace::Linear => self.into(),
+        }
+    }
+}
+
+/// The space user-supplied colours are written in.
+///
+/// Render targets with an sRGB format expect linear values from the shader and encode them on
+/// write, so sRGB colours (as picked in most tools) have to be linearised before upload or
+/// they come out too light.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorSpace {
+    /// Colours are sRGB and are converted to linear before upload.
+    #[default]
+    Srgb,
+    /// Colours are already linear and are uploaded untouched.
+    Linear,
+}
+
+/// Alternative spelling of [`Color`].
+pub type Colour = Color;
+
+/// The sRGB transfer function, inverted.
+fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+impl From<Color> for [f32; 4] {
+    fn from(c: Color) -> Self {
+        [c.r, c.g, c.b, c.a]
+    }
+}
+
+impl From<Color> for [f32; 3] {
+    fn from(c: Color) -> Self {
+        [c.r, c.g, c.b]
+    }
+}
+
+impl From<[f32; 3]> for Color {
+    fn from([r, g, b]: [f32; 3]) -> Self {
+        Self::rgb(r, g, b)
+    }
+}
+
+impl From<[f32; 4]> for Color {
+    fn from([r, g, b, a]: [f32; 4]) -> Self {
+        Self::rgba(r, g, b, a)
+    }
+}
+
+/// Why a hex colour string could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseColorError {
+    /// The string had this many digits instead of 3, 6 or 8.
+    InvalidLength(usize),
+    /// The string contained something other than hex digits.
+    InvalidDigit,
+}
+
+impl fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLength(len) => {
+                write!(f, "expected 3, 6 or 8 hex digits, found {len}")
+            }
+            Self::InvalidDigit => write!(f, "colour contains a non-hex digit"),
+        }
+    }
+}
+
+impl std::error::Error for ParseColorError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: [f32; 4], expected: [f32; 4]) {
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-4, "{actual:?} != {expected:?}");
+        }
+    }
+
+    #[test]
+    fn parses_three_digit_hex() {
+        let c = Color::from_hex("#f80").unwrap();
+        assert_close(c.into(), [1.0, 0x88 as f32 / 255.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn parses_six_digit_hex() {
+        let c = Color::from_hex("ff8800").unwrap();
+        assert_close(c.into(), [1.0, 0x88 as f32 / 255.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn parses_eight_digit_hex() {
+        let c = Color::from_hex("#FF880080").unwrap();
+        assert_close(
+            c.into(),
+            [1.0, 0x88 as f32 / 255.0, 0.0, 0x80 as f32 / 255.0],
+        );
+    }
+
+    #[test]
+    fn rejects_bad_hex() {
+        assert_eq!(
+            Color::from_hex("#ff88"),
+            Err(ParseColorError::InvalidLength(4))
+        );
+        assert_eq!(Color::from_hex(""), Err(ParseColorError::InvalidLength(0)));
+        assert_eq!(
+            Color::from_hex("#gg8800"),
+            Err(ParseColorError::InvalidDigit)
+        );
+        assert_eq!(
+            Color::from_hex("#ff88é"),
+            Err(ParseColorError::InvalidDigit)
+        );
+    }
+
+    #[test]
+    fn mid_grey_is_linearised_for_srgb_targets() {
+        let grey = Color::from_hex("#808080").unwrap();
+        assert_close(grey.to_gpu(ColorSpace::Srgb), [0.2158, 0.2158, 0.2158, 1.0]);
+        assert_close(grey.to_gpu(ColorSpace::Linear), grey.into());
+    }
+}
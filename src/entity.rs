@@ -0,0 +1,230 @@
+/// Fraction of live entities that may change in a frame before patching them one by one costs
+/// more than rebuilding everything.
+const REBUILD_FRACTION: f32 = 0.5;
+
+/// Handle to a spawned entity.
+///
+/// Handles carry a generation, so one kept after its entity was despawned stays invalid even
+/// once the slot is reused, instead of silently aliasing the new entity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Entity {
+    index: u32,
+    generation: u32,
+}
+
+/// Why an [`Entity`] handle could not be used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntityError {
+    /// The entity was despawned.
+    Stale,
+}
+
+impl std::fmt::Display for EntityError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Stale => write!(f, "entity was despawned"),
+        }
+    }
+}
+
+impl std::error::Error for EntityError {}
+
+/// What has to be re-uploaded since the last [`Entities::take_changes`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Changes {
+    None,
+    /// Only these entities changed, patch their slots in place.
+    Patch(Vec<Entity>),
+    /// Entities were spawned or despawned, or too many changed to patch.
+    Rebuild,
+}
+
+#[derive(Debug, Clone)]
+struct Slot<T> {
+    generation: u32,
+    value: Option<T>,
+    dirty: bool,
+}
+
+/// Retained entities addressed by generational handles, with change tracking to decide how
+/// much of the batched geometry needs rebuilding each frame.
+#[derive(Debug, Clone)]
+pub struct Entities<T> {
+    slots: Vec<Slot<T>>,
+    free: Vec<u32>,
+    dirty: Vec<u32>,
+    len: usize,
+    layout_changed: bool,
+}
+
+impl<T> Entities<T> {
+    pub fn new() -> Self {
+        Self {
+            slots: Vec::new(),
+            free: Vec::new(),
+            dirty: Vec::new(),
+            len: 0,
+            layout_changed: false,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Adds an entity, reusing the slot of a despawned one if there is any.
+    pub fn spawn(&mut self, value: T) -> Entity {
+        self.len += 1;
+        self.layout_changed = true;
+        if let Some(index) = self.free.pop() {
+            let slot = &mut self.slots[index as usize];
+            slot.value = Some(value);
+            return Entity {
+                index,
+                generation: slot.generation,
+            };
+        }
+        self.slots.push(Slot {
+            generation: 0,
+            value: Some(value),
+            dirty: false,
+        });
+        Entity {
+            index: self.slots.len() as u32 - 1,
+            generation: 0,
+        }
+    }
+
+    /// Removes an entity and returns its data. Every handle to it becomes stale.
+    pub fn despawn(&mut self, entity: Entity) -> Result<T, EntityError> {
+        let slot = self.slot_mut(entity)?;
+        let value = slot.value.take().ok_or(EntityError::Stale)?;
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free.push(entity.index);
+        self.len -= 1;
+        self.layout_changed = true;
+        Ok(value)
+    }
+
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.get(entity).is_ok()
+    }
+
+    pub fn get(&self, entity: Entity) -> Result<&T, EntityError> {
+        self.slots
+            .get(entity.index as usize)
+            .filter(|slot| slot.generation == entity.generation)
+            .and_then(|slot| slot.value.as_ref())
+            .ok_or(EntityError::Stale)
+    }
+
+    /// Mutable access to an entity's data. The entity is marked changed.
+    pub fn get_mut(&mut self, entity: Entity) -> Result<&mut T, EntityError> {
+        let slot = self
+            .slots
+            .get_mut(entity.index as usize)
+            .filter(|slot| slot.generation == entity.generation)
+            .ok_or(EntityError::Stale)?;
+        let value = slot.value.as_mut().ok_or(EntityError::Stale)?;
+        if !slot.dirty {
+            slot.dirty = true;
+            self.dirty.push(entity.index);
+        }
+        Ok(value)
+    }
+
+    /// Every live entity in slot order.
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, &T)> {
+        self.slots.iter().enumerate().filter_map(|(index, slot)| {
+            let entity = Entity {
+                index: index as u32,
+                generation: slot.generation,
+            };
+            slot.value.as_ref().map(|value| (entity, value))
+        })
+    }
+
+    /// Reports what changed since the last call and starts tracking afresh.
+    pub fn take_changes(&mut self) -> Changes {
+        let dirty = std::mem::take(&mut self.dirty);
+        for &index in &dirty {
+            self.slots[index as usize].dirty = false;
+        }
+        if std::mem::take(&mut self.layout_changed)
+            || dirty.len() as f32 > self.len as f32 * REBUILD_FRACTION
+        {
+            return Changes::Rebuild;
+        }
+        let patched: Vec<Entity> = dirty
+            .into_iter()
+            .filter_map(|index| {
+                let slot = &self.slots[index as usize];
+                slot.value.as_ref().map(|_| Entity {
+                    index,
+                    generation: slot.generation,
+                })
+            })
+            .collect();
+        if patched.is_empty() {
+            Changes::None
+        } else {
+            Changes::Patch(patched)
+        }
+    }
+
+    fn slot_mut(&mut self, entity: Entity) -> Result<&mut Slot<T>, EntityError> {
+        self.slots
+            .get_mut(entity.index as usize)
+            .filter(|slot| slot.generation == entity.generation)
+            .ok_or(EntityError::Stale)
+    }
+}
+
+impl<T> Default for Entities<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stale_handles_do_not_alias_reused_slots() {
+        let mut entities = Entities::new();
+        let first = entities.spawn("tank");
+        assert_eq!(entities.despawn(first), Ok("tank"));
+        let second = entities.spawn("turret");
+        assert_eq!(entities.len(), 1);
+        assert_eq!(entities.get(first), Err(EntityError::Stale));
+        assert_eq!(entities.get_mut(first), Err(EntityError::Stale));
+        assert_eq!(entities.despawn(first), Err(EntityError::Stale));
+        assert_eq!(entities.get(second), Ok(&"turret"));
+    }
+
+    #[test]
+    fn few_changes_patch_and_many_rebuild() {
+        let mut entities = Entities::new();
+        let handles: Vec<Entity> = (0..10).map(|i| entities.spawn(i)).collect();
+        assert_eq!(entities.take_changes(), Changes::Rebuild);
+        assert_eq!(entities.take_changes(), Changes::None);
+
+        *entities.get_mut(handles[3]).unwrap() += 100;
+        *entities.get_mut(handles[3]).unwrap() += 100;
+        assert_eq!(entities.take_changes(), Changes::Patch(vec![handles[3]]));
+
+        for &entity in &handles[..6] {
+            *entities.get_mut(entity).unwrap() += 1;
+        }
+        assert_eq!(entities.take_changes(), Changes::Rebuild);
+
+        entities.despawn(handles[0]).unwrap();
+        assert_eq!(entities.take_changes(), Changes::Rebuild);
+        assert_eq!(entities.iter().count(), 9);
+    }
+}
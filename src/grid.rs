@@ -0,0 +1,193 @@
+use crate::collision::Aabb;
+use crate::color::Color;
+
+/// Most lines drawn along either axis. When zoomed out further the spacing doubles until the
+/// grid fits, so a tiny spacing can't produce millions of lines.
+const MAX_LINES_PER_AXIS: usize = 256;
+
+/// A straight segment in world units.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Line {
+    pub from: [f32; 2],
+    pub to: [f32; 2],
+    pub colour: Color,
+}
+
+/// A world-space debug grid with the X and Y axes highlighted.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridConfig {
+    /// World units between grid lines.
+    pub spacing: f32,
+    pub colour: Color,
+    pub axis_colour: Color,
+}
+
+impl Default for GridConfig {
+    fn default() -> Self {
+        Self {
+            spacing: 1.0,
+            colour: Color::rgba(1.0, 1.0, 1.0, 0.2),
+            axis_colour: Color::WHITE,
+        }
+    }
+}
+
+impl GridConfig {
+    /// Grid lines covering `visible`, typically the camera's view, so the grid follows pan and
+    /// zoom. Lines through the origin use `axis_colour`.
+    pub fn lines(&self, visible: &Aabb) -> Vec<Line> {
+        let extent = visible.width().max(visible.height());
+        if self.spacing <= 0.0 || !self.spacing.is_finite() || !extent.is_finite() {
+            return Vec::new();
+        }
+        let mut spacing = self.spacing;
+        while extent / spacing > MAX_LINES_PER_AXIS as f32 {
+            spacing *= 2.0;
+        }
+
+        let mut lines = Vec::new();
+        for axis in 0..2 {
+            let first = (visible.min[axis] / spacing).ceil() as i64;
+            let last = (visible.max[axis] / spacing).floor() as i64;
+            for step in first..=last {
+                let at = step as f32 * spacing;
+                let (from, to) = if axis == 0 {
+                    ([at, visible.min[1]], [at, visible.max[1]])
+                } else {
+                    ([visible.min[0], at], [visible.max[0], at])
+                };
+                let colour = if step == 0 {
+                    self.axis_colour
+                } else {
+                    self.colour
+                };
+                lines.push(Line { from, to, colour });
+            }
+        }
+        lines
+    }
+}
+
+/// Debug overlay outlining the axis-aligned bounds of every drawn item, drawn after the
+/// frame's normal content.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DebugBounds {
+    pub enabled: bool,
+    pub colour: Color,
+}
+
+impl Default for DebugBounds {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            colour: Color::MAGENTA,
+        }
+    }
+}
+
+impl DebugBounds {
+    /// Four outline segments per box in `bounds`, or nothing while disabled.
+    ///
+    /// Only the items' own bounds go in, so the outlines are never outlined themselves. For a
+    /// rotated item pass [`Aabb::from_transform`] to outline its enclosing box.
+    pub fn lines(&self, bounds: impl IntoIterator<Item = Aabb>) -> Vec<Line> {
+        if !self.enabled {
+            return Vec::new();
+        }
+        let colour = self.colour;
+        bounds
+            .into_iter()
+            .flat_map(|b| {
+                let corners = [b.min, [b.max[0], b.min[1]], b.max, [b.min[0], b.max[1]]];
+                (0..4).map(move |i| Line {
+                    from: corners[i],
+                    to: corners[(i + 1) % 4],
+                    colour,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_4;
+
+    use super::*;
+    use crate::transform::Transform2D;
+
+    #[test]
+    fn lines_cover_view_and_highlight_axes() {
+        let grid = GridConfig {
+            spacing: 2.0,
+            ..GridConfig::default()
+        };
+        let lines = grid.lines(&Aabb::new([-3.0, -1.0], [5.0, 3.0]));
+        let vertical: Vec<f32> = lines
+            .iter()
+            .filter(|l| l.from[0] == l.to[0])
+            .map(|l| l.from[0])
+            .collect();
+        assert_eq!(vertical, vec![-2.0, 0.0, 2.0, 4.0]);
+        assert_eq!(lines.len(), 4 + 2);
+        let axes: Vec<&Line> = lines
+            .iter()
+            .filter(|l| l.colour == grid.axis_colour)
+            .collect();
+        assert_eq!(axes.len(), 2);
+        assert!(axes.iter().all(|l| l.from[0] == 0.0 || l.from[1] == 0.0));
+        assert_eq!(lines[0].from, [-2.0, -1.0]);
+        assert_eq!(lines[0].to, [-2.0, 3.0]);
+    }
+
+    #[test]
+    fn zooming_out_coarsens_the_grid() {
+        let grid = GridConfig {
+            spacing: 0.001,
+            ..GridConfig::default()
+        };
+        let lines = grid.lines(&Aabb::new([-500.0, -500.0], [500.0, 500.0]));
+        assert!(lines.len() <= 2 * (MAX_LINES_PER_AXIS + 1));
+        assert!(!lines.is_empty());
+    }
+
+    #[test]
+    fn rotated_items_are_outlined_by_their_enclosing_box() {
+        let square = Transform2D {
+            translation: [1.0, 1.0],
+            rotation: FRAC_PI_4,
+            scale: [2.0, 2.0],
+        };
+        let overlay = DebugBounds {
+            enabled: true,
+            ..DebugBounds::default()
+        };
+        let lines = overlay.lines([Aabb::from_transform(&square)]);
+        assert_eq!(lines.len(), 4);
+        let half = 2f32.sqrt();
+        for line in &lines {
+            for [x, y] in [line.from, line.to] {
+                assert!(((x - 1.0).abs() - half).abs() < 1e-5, "{x}");
+                assert!(((y - 1.0).abs() - half).abs() < 1e-5, "{y}");
+            }
+            assert_eq!(line.colour, Color::MAGENTA);
+        }
+    }
+
+    #[test]
+    fn overlay_is_not_fed_back_into_itself() {
+        let items = [
+            Aabb::new([0.0, 0.0], [1.0, 1.0]),
+            Aabb::new([2.0, 0.0], [3.0, 2.0]),
+        ];
+        let overlay = DebugBounds {
+            enabled: true,
+            ..DebugBounds::default()
+        };
+        let first = overlay.lines(items);
+        let second = overlay.lines(items);
+        assert_eq!(first.len(), 4 * items.len());
+        assert_eq!(first, second);
+        assert!(DebugBounds::default().lines(items).is_empty());
+    }
+}
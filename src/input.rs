@@ -0,0 +1,949 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Copy)]
+enum Edge {
+    Down,
+    Pressed,
+    Released,
+}
+
+/// Held buttons plus the ones that changed since the last [`Buttons::end_frame`].
+#[derive(Debug, Clone)]
+struct Buttons<T> {
+    down: HashSet<T>,
+    pressed: HashSet<T>,
+    released: HashSet<T>,
+}
+
+impl<T: Copy + Eq + Hash> Buttons<T> {
+    fn new() -> Self {
+        Self {
+            down: HashSet::new(),
+            pressed: HashSet::new(),
+            released: HashSet::new(),
+        }
+    }
+
+    fn press(&mut self, button: T) {
+        // Pressing an already held button is an OS key repeat, not a new press.
+        if self.down.insert(button) {
+            self.pressed.insert(button);
+        }
+    }
+
+    fn release(&mut self, button: T) {
+        if self.down.remove(&button) {
+            self.released.insert(button);
+        }
+    }
+
+    fn is(&self, edge: Edge, button: &T) -> bool {
+        match edge {
+            Edge::Down => self.down.contains(button),
+            Edge::Pressed => self.pressed.contains(button),
+            Edge::Released => self.released.contains(button),
+        }
+    }
+
+    fn end_frame(&mut self) {
+        self.pressed.clear();
+        self.released.clear();
+    }
+}
+
+/// Pixel scroll distance treated as one line, to put touchpad and wheel scrolling on one scale.
+const PIXELS_PER_LINE: f32 = 20.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle,
+    Other(u16),
+}
+
+/// A scroll event as reported by the windowing layer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScrollDelta {
+    /// Whole lines, as from a notched mouse wheel.
+    Lines([f32; 2]),
+    /// Pixels, as from a touchpad.
+    Pixels([f32; 2]),
+}
+
+/// Which modifier keys are held.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Modifiers {
+    pub shift: bool,
+    pub ctrl: bool,
+    pub alt: bool,
+    /// The Windows, Command or Super key.
+    pub logo: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TouchPhase {
+    Started,
+    Moved,
+    Ended,
+    Cancelled,
+}
+
+/// A finger on the screen, position in physical pixels from the window's top-left.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Touch {
+    pub id: u64,
+    pub phase: TouchPhase,
+    pub position: [f32; 2],
+}
+
+/// Default radius around the centre inside which stick and trigger values read as zero.
+const DEFAULT_DEADZONE: f32 = 0.1;
+
+/// Gamepad buttons, named by position so layouts from different vendors line up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamepadButton {
+    South,
+    East,
+    North,
+    West,
+    LeftBumper,
+    RightBumper,
+    LeftTrigger,
+    RightTrigger,
+    Select,
+    Start,
+    LeftStick,
+    RightStick,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+    Other(u16),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamepadAxis {
+    LeftStickX,
+    LeftStickY,
+    RightStickX,
+    RightStickY,
+    LeftTrigger,
+    RightTrigger,
+}
+
+/// A controller being plugged in or removed. `id` is the backend's identifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GamepadEvent {
+    Connected(u64),
+    Disconnected(u64),
+}
+
+/// Button and axis state of one connected controller.
+#[derive(Debug, Clone)]
+pub struct Gamepad {
+    id: u64,
+    buttons: Buttons<GamepadButton>,
+    axes: HashMap<GamepadAxis, f32>,
+    deadzone: f32,
+}
+
+impl Gamepad {
+    /// The backend's identifier for this controller.
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn is_button_down(&self, button: GamepadButton) -> bool {
+        self.buttons.is(Edge::Down, &button)
+    }
+
+    pub fn is_button_just_pressed(&self, button: GamepadButton) -> bool {
+        self.buttons.is(Edge::Pressed, &button)
+    }
+
+    pub fn is_button_just_released(&self, button: GamepadButton) -> bool {
+        self.buttons.is(Edge::Released, &button)
+    }
+
+    /// Axis value in -1..1 (0..1 for triggers) with the deadzone cut out and the rest rescaled,
+    /// so values still reach the full range.
+    pub fn axis(&self, axis: GamepadAxis) -> f32 {
+        let value = self.axes.get(&axis).copied().unwrap_or(0.0);
+        if value.abs() <= self.deadzone {
+            return 0.0;
+        }
+        value.signum() * ((value.abs() - self.deadzone) / (1.0 - self.deadzone)).min(1.0)
+    }
+}
+
+/// An editing key received while typing, kept apart from the typed text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextEdit {
+    Backspace,
+    Enter,
+}
+
+/// Something an action can be bound to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Binding<K> {
+    Key(K),
+    Mouse(MouseButton),
+}
+
+/// Named actions ("jump", "move_left") bound to one or more keys or mouse buttons.
+///
+/// The same binding may be used by several actions, in which case all of them fire.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputMap<K> {
+    actions: HashMap<String, Vec<Binding<K>>>,
+}
+
+impl<K: Copy + Eq + Hash> InputMap<K> {
+    pub fn new() -> Self {
+        Self {
+            actions: HashMap::new(),
+        }
+    }
+
+    /// Adds `binding` to `action`, creating the action if needed.
+    pub fn bind(&mut self, action: &str, binding: Binding<K>) {
+        let bindings = self.actions.entry(action.to_owned()).or_default();
+        if !bindings.contains(&binding) {
+            bindings.push(binding);
+        }
+    }
+
+    pub fn unbind(&mut self, action: &str, binding: Binding<K>) {
+        if let Some(bindings) = self.actions.get_mut(action) {
+            bindings.retain(|b| *b != binding);
+        }
+    }
+
+    /// Removes every binding of `action`.
+    pub fn clear(&mut self, action: &str) {
+        self.actions.remove(action);
+    }
+
+    pub fn bindings(&self, action: &str) -> &[Binding<K>] {
+        self.actions.get(action).map_or(&[], Vec::as_slice)
+    }
+
+    /// Every action and its bindings, for saving or a rebinding menu.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[Binding<K>])> {
+        self.actions
+            .iter()
+            .map(|(action, bindings)| (action.as_str(), bindings.as_slice()))
+    }
+}
+
+impl<K: Copy + Eq + Hash> Default for InputMap<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Keyboard and mouse state for the current frame.
+///
+/// `K` is whatever key type the windowing layer reports. Feed it key events as they arrive
+/// and call [`InputState::end_frame`] once at the end of every frame.
+#[derive(Debug, Clone)]
+pub struct InputState<K> {
+    keys: Buttons<K>,
+    modifiers: Modifiers,
+    mouse: Buttons<MouseButton>,
+    cursor: Option<[f32; 2]>,
+    scroll: [f32; 2],
+    actions: InputMap<K>,
+    text_input: bool,
+    text: String,
+    text_edits: Vec<TextEdit>,
+    touches: Vec<Touch>,
+    primary_touch: Option<u64>,
+    touch_emulates_mouse: bool,
+    hovered_files: Vec<PathBuf>,
+    dropped_files: Vec<PathBuf>,
+    gamepads: Vec<Gamepad>,
+    gamepad_events: Vec<GamepadEvent>,
+    deadzone: f32,
+}
+
+impl<K: Copy + Eq + Hash> InputState<K> {
+    pub fn new() -> Self {
+        Self {
+            keys: Buttons::new(),
+            modifiers: Modifiers::default(),
+            mouse: Buttons::new(),
+            cursor: None,
+            scroll: [0.0, 0.0],
+            actions: InputMap::new(),
+            text_input: false,
+            text: String::new(),
+            text_edits: Vec::new(),
+            touches: Vec::new(),
+            primary_touch: None,
+            touch_emulates_mouse: true,
+            hovered_files: Vec::new(),
+            dropped_files: Vec::new(),
+            gamepads: Vec::new(),
+            gamepad_events: Vec::new(),
+            deadzone: DEFAULT_DEADZONE,
+        }
+    }
+
+    /// Records a key press. Repeats of a key that is already down are ignored, and so are all
+    /// presses while text input is enabled.
+    pub fn press(&mut self, key: K) {
+        if !self.text_input {
+            self.keys.press(key);
+        }
+    }
+
+    pub fn release(&mut self, key: K) {
+        self.keys.release(key);
+    }
+
+    pub fn is_key_down(&self, key: K) -> bool {
+        self.keys.down.contains(&key)
+    }
+
+    /// Whether `key` went down this frame. A tap pressed and released within one frame still
+    /// counts.
+    pub fn is_key_just_pressed(&self, key: K) -> bool {
+        self.keys.pressed.contains(&key)
+    }
+
+    /// Whether `key` went up this frame.
+    pub fn is_key_just_released(&self, key: K) -> bool {
+        self.keys.released.contains(&key)
+    }
+
+    /// Records the modifier state reported by the windowing layer whenever it changes.
+    pub fn set_modifiers(&mut self, modifiers: Modifiers) {
+        self.modifiers = modifiers;
+    }
+
+    pub fn modifiers(&self) -> Modifiers {
+        self.modifiers
+    }
+
+    pub fn ctrl(&self) -> bool {
+        self.modifiers.ctrl
+    }
+
+    pub fn shift(&self) -> bool {
+        self.modifiers.shift
+    }
+
+    pub fn alt(&self) -> bool {
+        self.modifiers.alt
+    }
+
+    pub fn press_mouse(&mut self, button: MouseButton) {
+        self.mouse.press(button);
+    }
+
+    pub fn release_mouse(&mut self, button: MouseButton) {
+        self.mouse.release(button);
+    }
+
+    pub fn is_mouse_down(&self, button: MouseButton) -> bool {
+        self.mouse.down.contains(&button)
+    }
+
+    pub fn is_mouse_just_pressed(&self, button: MouseButton) -> bool {
+        self.mouse.pressed.contains(&button)
+    }
+
+    pub fn is_mouse_just_released(&self, button: MouseButton) -> bool {
+        self.mouse.released.contains(&button)
+    }
+
+    /// Records the cursor position in physical pixels from the window's top-left.
+    pub fn cursor_moved(&mut self, position: [f32; 2]) {
+        self.cursor = Some(position);
+    }
+
+    pub fn cursor_left(&mut self) {
+        self.cursor = None;
+    }
+
+    /// Cursor position in physical pixels, `None` while it is outside the window.
+    pub fn cursor(&self) -> Option<[f32; 2]> {
+        self.cursor
+    }
+
+    pub fn scroll(&mut self, delta: ScrollDelta) {
+        let [x, y] = match delta {
+            ScrollDelta::Lines(lines) => lines,
+            ScrollDelta::Pixels([x, y]) => [x / PIXELS_PER_LINE, y / PIXELS_PER_LINE],
+        };
+        self.scroll[0] += x;
+        self.scroll[1] += y;
+    }
+
+    /// Scrolling this frame, in lines.
+    pub fn scroll_delta(&self) -> [f32; 2] {
+        self.scroll
+    }
+
+    pub fn actions(&self) -> &InputMap<K> {
+        &self.actions
+    }
+
+    /// The action bindings, mutable at runtime for rebinding.
+    pub fn actions_mut(&mut self) -> &mut InputMap<K> {
+        &mut self.actions
+    }
+
+    /// Whether any binding of `action` is held.
+    pub fn action_down(&self, action: &str) -> bool {
+        self.any_binding(action, Edge::Down)
+    }
+
+    /// Whether any binding of `action` went down this frame.
+    pub fn action_just_pressed(&self, action: &str) -> bool {
+        self.any_binding(action, Edge::Pressed)
+    }
+
+    /// Whether any binding of `action` went up this frame.
+    pub fn action_just_released(&self, action: &str) -> bool {
+        self.any_binding(action, Edge::Released)
+    }
+
+    fn any_binding(&self, action: &str, edge: Edge) -> bool {
+        self.actions
+            .bindings(action)
+            .iter()
+            .any(|binding| match binding {
+                Binding::Key(key) => self.keys.is(edge, key),
+                Binding::Mouse(button) => self.mouse.is(edge, button),
+            })
+    }
+
+    /// Switches between gameplay keys and typing. While enabled, characters are collected
+    /// into [`InputState::chars`] and key presses are not recorded, so typing a name doesn't
+    /// also move the player. Releases still are, so no key gets stuck down.
+    pub fn set_text_input_enabled(&mut self, enabled: bool) {
+        self.text_input = enabled;
+    }
+
+    pub fn is_text_input_enabled(&self) -> bool {
+        self.text_input
+    }
+
+    /// Records a typed character. Backspace and Enter become [`TextEdit`]s, other control
+    /// characters are dropped.
+    pub fn receive_char(&mut self, c: char) {
+        if !self.text_input {
+            return;
+        }
+        match c {
+            '\u{8}' => self.text_edits.push(TextEdit::Backspace),
+            '\r' | '\n' => self.text_edits.push(TextEdit::Enter),
+            c if c.is_control() => {}
+            c => self.text.push(c),
+        }
+    }
+
+    /// Records text committed by an input method, which may be several characters at once.
+    pub fn commit_text(&mut self, text: &str) {
+        if self.text_input {
+            self.text.extend(text.chars().filter(|c| !c.is_control()));
+        }
+    }
+
+    /// Text typed this frame.
+    pub fn chars(&self) -> &str {
+        &self.text
+    }
+
+    /// Editing keys received this frame, in order.
+    pub fn text_edits(&self) -> &[TextEdit] {
+        &self.text_edits
+    }
+
+    /// Records a touch event.
+    ///
+    /// Unless turned off with [`InputState::set_touch_emulates_mouse`], the first finger down
+    /// also drives the cursor and the left mouse button, so mouse-only games work on touch
+    /// screens unchanged.
+    pub fn touch(&mut self, id: u64, phase: TouchPhase, position: [f32; 2]) {
+        let touch = Touch {
+            id,
+            phase,
+            position,
+        };
+        match self.touches.iter_mut().find(|t| t.id == id) {
+            Some(existing) => *existing = touch,
+            None => self.touches.push(touch),
+        }
+
+        if !self.touch_emulates_mouse {
+            return;
+        }
+        if phase == TouchPhase::Started && self.primary_touch.is_none() {
+            self.primary_touch = Some(id);
+        }
+        if self.primary_touch != Some(id) {
+            return;
+        }
+        self.cursor_moved(position);
+        match phase {
+            TouchPhase::Started => self.press_mouse(MouseButton::Left),
+            TouchPhase::Moved => {}
+            TouchPhase::Ended | TouchPhase::Cancelled => {
+                self.release_mouse(MouseButton::Left);
+                self.primary_touch = None;
+            }
+        }
+    }
+
+    /// Whether the first touch drives the cursor and left mouse button. On by default; turning
+    /// it off releases the button if a touch is holding it.
+    pub fn set_touch_emulates_mouse(&mut self, enabled: bool) {
+        if !enabled && self.primary_touch.take().is_some() {
+            self.release_mouse(MouseButton::Left);
+        }
+        self.touch_emulates_mouse = enabled;
+    }
+
+    pub fn touch_emulates_mouse(&self) -> bool {
+        self.touch_emulates_mouse
+    }
+
+    /// Active touches, plus any that ended or were cancelled this frame.
+    pub fn touches(&self) -> &[Touch] {
+        &self.touches
+    }
+
+    /// Records a file being dragged over the window. Several files arrive one event each.
+    pub fn file_hovered(&mut self, path: PathBuf) {
+        self.hovered_files.push(path);
+    }
+
+    pub fn file_hover_cancelled(&mut self) {
+        self.hovered_files.clear();
+    }
+
+    pub fn file_dropped(&mut self, path: PathBuf) {
+        self.hovered_files.clear();
+        self.dropped_files.push(path);
+    }
+
+    /// Files currently dragged over the window, for a "drop here" highlight.
+    pub fn hovered_files(&self) -> &[PathBuf] {
+        &self.hovered_files
+    }
+
+    pub fn is_file_hovered(&self) -> bool {
+        !self.hovered_files.is_empty()
+    }
+
+    /// Files dropped onto the window this frame.
+    pub fn dropped_files(&self) -> &[PathBuf] {
+        &self.dropped_files
+    }
+
+    /// Records a controller being plugged in. It becomes addressable by its index in
+    /// [`InputState::gamepads`], in connection order.
+    pub fn gamepad_connected(&mut self, id: u64) {
+        if self.gamepads.iter().any(|pad| pad.id == id) {
+            return;
+        }
+        self.gamepads.push(Gamepad {
+            id,
+            buttons: Buttons::new(),
+            axes: HashMap::new(),
+            deadzone: self.deadzone,
+        });
+        self.gamepad_events.push(GamepadEvent::Connected(id));
+    }
+
+    /// Records a controller being removed. Controllers after it move down one index.
+    pub fn gamepad_disconnected(&mut self, id: u64) {
+        let before = self.gamepads.len();
+        self.gamepads.retain(|pad| pad.id != id);
+        if self.gamepads.len() != before {
+            self.gamepad_events.push(GamepadEvent::Disconnected(id));
+        }
+    }
+
+    pub fn press_gamepad_button(&mut self, id: u64, button: GamepadButton) {
+        if let Some(pad) = self.gamepad_by_id(id) {
+            pad.buttons.press(button);
+        }
+    }
+
+    pub fn release_gamepad_button(&mut self, id: u64, button: GamepadButton) {
+        if let Some(pad) = self.gamepad_by_id(id) {
+            pad.buttons.release(button);
+        }
+    }
+
+    /// Records the raw value of an axis, before the deadzone is applied.
+    pub fn gamepad_axis_changed(&mut self, id: u64, axis: GamepadAxis, value: f32) {
+        if let Some(pad) = self.gamepad_by_id(id) {
+            pad.axes.insert(axis, value);
+        }
+    }
+
+    /// Sets the deadzone applied to every controller's axes, clamped to 0..0.99.
+    pub fn set_gamepad_deadzone(&mut self, deadzone: f32) {
+        self.deadzone = deadzone.clamp(0.0, 0.99);
+        for pad in &mut self.gamepads {
+            pad.deadzone = self.deadzone;
+        }
+    }
+
+    /// Connected controllers in connection order.
+    pub fn gamepads(&self) -> &[Gamepad] {
+        &self.gamepads
+    }
+
+    pub fn gamepad(&self, index: usize) -> Option<&Gamepad> {
+        self.gamepads.get(index)
+    }
+
+    /// `axis` of the first connected controller, 0 without one.
+    pub fn gamepad_axis(&self, axis: GamepadAxis) -> f32 {
+        self.gamepad(0).map_or(0.0, |pad| pad.axis(axis))
+    }
+
+    /// Whether `button` is held on any connected controller.
+    pub fn gamepad_button_down(&self, button: GamepadButton) -> bool {
+        self.gamepads.iter().any(|pad| pad.is_button_down(button))
+    }
+
+    /// Whether `button` was pressed this frame on any connected controller.
+    pub fn gamepad_button_just_pressed(&self, button: GamepadButton) -> bool {
+        self.gamepads
+            .iter()
+            .any(|pad| pad.is_button_just_pressed(button))
+    }
+
+    /// Controllers connected or disconnected this frame.
+    pub fn gamepad_events(&self) -> &[GamepadEvent] {
+        &self.gamepad_events
+    }
+
+    fn gamepad_by_id(&mut self, id: u64) -> Option<&mut Gamepad> {
+        self.gamepads.iter_mut().find(|pad| pad.id == id)
+    }
+
+    /// Clears this frame's "just" states, scrolling, text, finished touches, dropped files and
+    /// gamepad events. Call once after update.
+    pub fn end_frame(&mut self) {
+        self.dropped_files.clear();
+        self.touches
+            .retain(|t| matches!(t.phase, TouchPhase::Started | TouchPhase::Moved));
+        self.keys.end_frame();
+        self.mouse.end_frame();
+        self.scroll = [0.0, 0.0];
+        self.text.clear();
+        self.text_edits.clear();
+        for pad in &mut self.gamepads {
+            pad.buttons.end_frame();
+        }
+        self.gamepad_events.clear();
+    }
+}
+
+impl<K: Copy + Eq + Hash> Default for InputState<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn held_key_is_just_pressed_only_on_first_frame() {
+        let mut input = InputState::new();
+        input.press('a');
+        assert!(input.is_key_just_pressed('a'));
+        input.end_frame();
+
+        // Held, and the OS repeats the press.
+        input.press('a');
+        assert!(input.is_key_down('a'));
+        assert!(!input.is_key_just_pressed('a'));
+        input.end_frame();
+
+        input.release('a');
+        assert!(input.is_key_just_released('a'));
+        input.end_frame();
+        assert!(!input.is_key_just_released('a'));
+    }
+
+    #[test]
+    fn tap_within_one_frame_registers() {
+        let mut input = InputState::new();
+        input.press('a');
+        input.release('a');
+        assert!(!input.is_key_down('a'));
+        assert!(input.is_key_just_pressed('a'));
+        assert!(input.is_key_just_released('a'));
+    }
+
+    #[test]
+    fn repeat_in_same_frame_as_press_counts_once() {
+        let mut input = InputState::new();
+        input.press('a');
+        input.press('a');
+        input.release('a');
+        input.end_frame();
+        input.press('a');
+        assert!(input.is_key_just_pressed('a'));
+    }
+
+    #[test]
+    fn mouse_buttons_have_edges() {
+        let mut input = InputState::<char>::new();
+        input.press_mouse(MouseButton::Left);
+        assert!(input.is_mouse_down(MouseButton::Left));
+        assert!(input.is_mouse_just_pressed(MouseButton::Left));
+        input.end_frame();
+        assert!(!input.is_mouse_just_pressed(MouseButton::Left));
+        input.release_mouse(MouseButton::Left);
+        assert!(input.is_mouse_just_released(MouseButton::Left));
+        assert!(!input.is_mouse_down(MouseButton::Right));
+    }
+
+    #[test]
+    fn cursor_leaving_window_clears_position() {
+        let mut input = InputState::<char>::new();
+        assert_eq!(input.cursor(), None);
+        input.cursor_moved([10.0, 20.0]);
+        assert_eq!(input.cursor(), Some([10.0, 20.0]));
+        input.cursor_left();
+        assert_eq!(input.cursor(), None);
+    }
+
+    #[test]
+    fn scroll_accumulates_in_lines_per_frame() {
+        let mut input = InputState::<char>::new();
+        input.scroll(ScrollDelta::Lines([0.0, 1.0]));
+        input.scroll(ScrollDelta::Pixels([0.0, PIXELS_PER_LINE * 2.0]));
+        assert_eq!(input.scroll_delta(), [0.0, 3.0]);
+        input.end_frame();
+        assert_eq!(input.scroll_delta(), [0.0, 0.0]);
+    }
+
+    #[test]
+    fn actions_fire_from_any_binding() {
+        let mut input = InputState::new();
+        input.actions_mut().bind("jump", Binding::Key(' '));
+        input
+            .actions_mut()
+            .bind("jump", Binding::Mouse(MouseButton::Left));
+        input
+            .actions_mut()
+            .bind("fire", Binding::Mouse(MouseButton::Left));
+
+        input.press_mouse(MouseButton::Left);
+        assert!(input.action_just_pressed("jump"));
+        assert!(input.action_down("fire"));
+        assert!(!input.action_down("crouch"));
+        input.end_frame();
+
+        input.release_mouse(MouseButton::Left);
+        assert!(input.action_just_released("jump"));
+    }
+
+    #[test]
+    fn actions_can_be_rebound() {
+        let mut input = InputState::new();
+        input.actions_mut().bind("jump", Binding::Key(' '));
+        input.actions_mut().unbind("jump", Binding::Key(' '));
+        input.actions_mut().bind("jump", Binding::Key('w'));
+        assert_eq!(input.actions().bindings("jump"), &[Binding::Key('w')]);
+
+        input.press(' ');
+        assert!(!input.action_down("jump"));
+        input.press('w');
+        assert!(input.action_down("jump"));
+    }
+
+    #[test]
+    fn text_is_collected_per_frame_only_when_enabled() {
+        let mut input = InputState::<char>::new();
+        input.receive_char('x');
+        assert_eq!(input.chars(), "");
+
+        input.set_text_input_enabled(true);
+        input.receive_char('é');
+        input.commit_text("日本");
+        input.receive_char('\u{8}');
+        input.receive_char('\r');
+        input.receive_char('\u{1b}');
+        assert_eq!(input.chars(), "é日本");
+        assert_eq!(input.text_edits(), &[TextEdit::Backspace, TextEdit::Enter]);
+
+        input.end_frame();
+        assert_eq!(input.chars(), "");
+        assert!(input.text_edits().is_empty());
+    }
+
+    #[test]
+    fn typing_does_not_press_gameplay_keys() {
+        let mut input = InputState::new();
+        input.press('w');
+        input.set_text_input_enabled(true);
+        input.press('a');
+        assert!(!input.is_key_down('a'));
+        input.release('w');
+        assert!(!input.is_key_down('w'));
+    }
+
+    #[test]
+    fn first_touch_acts_as_left_mouse() {
+        let mut input = InputState::<char>::new();
+        input.touch(7, TouchPhase::Started, [5.0, 5.0]);
+        input.touch(8, TouchPhase::Started, [50.0, 50.0]);
+        assert_eq!(input.touches().len(), 2);
+        assert!(input.is_mouse_just_pressed(MouseButton::Left));
+        assert_eq!(input.cursor(), Some([5.0, 5.0]));
+
+        input.touch(8, TouchPhase::Moved, [60.0, 60.0]);
+        assert_eq!(input.cursor(), Some([5.0, 5.0]));
+        input.touch(7, TouchPhase::Moved, [6.0, 6.0]);
+        assert_eq!(input.cursor(), Some([6.0, 6.0]));
+    }
+
+    #[test]
+    fn cancelled_touch_does_not_stay_down() {
+        let mut input = InputState::<char>::new();
+        input.touch(1, TouchPhase::Started, [0.0, 0.0]);
+        input.end_frame();
+        input.touch(1, TouchPhase::Cancelled, [0.0, 0.0]);
+        assert!(!input.is_mouse_down(MouseButton::Left));
+        assert_eq!(input.touches()[0].phase, TouchPhase::Cancelled);
+        input.end_frame();
+        assert!(input.touches().is_empty());
+    }
+
+    #[test]
+    fn touch_appears_then_disappears_without_mouse_emulation() {
+        let mut input = InputState::<char>::new();
+        input.set_touch_emulates_mouse(false);
+        input.touch(3, TouchPhase::Started, [1.0, 2.0]);
+        assert_eq!(input.touches().len(), 1);
+        input.end_frame();
+
+        input.touch(3, TouchPhase::Moved, [4.0, 2.0]);
+        assert_eq!(input.touches()[0].position, [4.0, 2.0]);
+        assert_eq!(input.touches()[0].phase, TouchPhase::Moved);
+        input.end_frame();
+
+        input.touch(3, TouchPhase::Ended, [4.0, 2.0]);
+        assert_eq!(input.touches()[0].phase, TouchPhase::Ended);
+        input.end_frame();
+        assert!(input.touches().is_empty());
+        assert!(!input.is_mouse_down(MouseButton::Left));
+        assert_eq!(input.cursor(), None);
+    }
+
+    #[test]
+    fn disabling_touch_emulation_releases_mouse() {
+        let mut input = InputState::<char>::new();
+        input.touch(1, TouchPhase::Started, [0.0, 0.0]);
+        assert!(input.is_mouse_down(MouseButton::Left));
+        input.set_touch_emulates_mouse(false);
+        assert!(!input.is_mouse_down(MouseButton::Left));
+        assert_eq!(input.touches().len(), 1);
+    }
+
+    #[test]
+    fn dropped_files_arrive_together_for_one_frame() {
+        let mut input = InputState::<char>::new();
+        input.file_hovered("a.png".into());
+        input.file_hovered("b.png".into());
+        assert!(input.is_file_hovered());
+        assert_eq!(input.hovered_files().len(), 2);
+
+        input.file_dropped("a.png".into());
+        input.file_dropped("b.png".into());
+        assert!(!input.is_file_hovered());
+        assert_eq!(
+            input.dropped_files(),
+            &[PathBuf::from("a.png"), PathBuf::from("b.png")]
+        );
+        input.end_frame();
+        assert!(input.dropped_files().is_empty());
+    }
+
+    #[test]
+    fn cancelled_hover_clears_highlight() {
+        let mut input = InputState::<char>::new();
+        input.file_hovered("a.png".into());
+        input.file_hover_cancelled();
+        assert!(!input.is_file_hovered());
+        assert!(input.dropped_files().is_empty());
+    }
+
+    #[test]
+    fn modifier_changes_are_reflected() {
+        let mut input = InputState::new();
+        assert!(!input.ctrl());
+        input.set_modifiers(Modifiers {
+            ctrl: true,
+            ..Modifiers::default()
+        });
+        input.press('s');
+        assert!(input.ctrl() && input.is_key_just_pressed('s'));
+        assert!(!input.shift() && !input.alt());
+        input.end_frame();
+        assert!(input.ctrl());
+        input.set_modifiers(Modifiers::default());
+        assert!(!input.ctrl());
+    }
+
+    #[test]
+    fn gamepads_are_addressed_in_connection_order() {
+        let mut input = InputState::<char>::new();
+        input.gamepad_connected(40);
+        input.gamepad_connected(7);
+        input.gamepad_connected(7);
+        assert_eq!(
+            input.gamepad_events(),
+            [GamepadEvent::Connected(40), GamepadEvent::Connected(7)]
+        );
+        input.press_gamepad_button(7, GamepadButton::South);
+        assert!(input
+            .gamepad(1)
+            .unwrap()
+            .is_button_just_pressed(GamepadButton::South));
+        assert!(!input
+            .gamepad(0)
+            .unwrap()
+            .is_button_down(GamepadButton::South));
+        assert!(input.gamepad_button_down(GamepadButton::South));
+
+        input.end_frame();
+        assert!(input.gamepad_events().is_empty());
+        assert!(!input.gamepad_button_just_pressed(GamepadButton::South));
+        input.gamepad_disconnected(40);
+        assert_eq!(input.gamepad_events(), [GamepadEvent::Disconnected(40)]);
+        assert_eq!(input.gamepad(0).unwrap().id(), 7);
+        assert!(input.gamepad_button_down(GamepadButton::South));
+    }
+
+    #[test]
+    fn gamepad_axes_apply_deadzone() {
+        let mut input = InputState::<char>::new();
+        assert_eq!(input.gamepad_axis(GamepadAxis::LeftStickX), 0.0);
+        input.gamepad_connected(1);
+        input.set_gamepad_deadzone(0.2);
+        input.gamepad_axis_changed(1, GamepadAxis::LeftStickX, 0.15);
+        assert_eq!(input.gamepad_axis(GamepadAxis::LeftStickX), 0.0);
+        input.gamepad_axis_changed(1, GamepadAxis::LeftStickX, -0.6);
+        assert!((input.gamepad_axis(GamepadAxis::LeftStickX) + 0.5).abs() < 1e-6);
+        input.gamepad_axis_changed(1, GamepadAxis::LeftStickX, 1.0);
+        assert_eq!(input.gamepad_axis(GamepadAxis::LeftStickX), 1.0);
+    }
+}
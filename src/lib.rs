@@ -1,3 +1,22 @@
+pub mod animation;
+pub mod collision;
+pub mod color;
+pub mod entity;
+pub mod grid;
+pub mod input;
+pub mod mesh;
+pub mod nine_slice;
+pub mod particle;
+pub mod pick;
+pub mod redraw;
+pub mod spatial;
+pub mod sprite;
+pub mod tilemap;
+pub mod time;
+pub mod timer;
+pub mod transform;
+pub mod tween;
+
 pub fn keep(){
 
 }
\ No newline at end of file
@@ -0,0 +1,211 @@
+use crate::color::Color;
+
+/// Indexed triangle-list geometry in world units, ready to be appended to a batch.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Mesh {
+    pub positions: Vec<[f32; 2]>,
+    /// Three indices into `positions` per triangle, counter-clockwise.
+    pub indices: Vec<u32>,
+}
+
+impl Mesh {
+    pub fn triangle_count(&self) -> usize {
+        self.indices.len() / 3
+    }
+}
+
+/// Polygons whose area is below this fraction of their bounding square are treated as flat,
+/// since nearly collinear float input rarely sums to exactly zero.
+const MIN_AREA_FRACTION: f32 = 1e-6;
+
+/// A filled convex polygon.
+///
+/// It is triangulated as a fan from the first point, which is only correct for convex shapes.
+/// Concave polygons will render incorrectly; triangulate them beforehand and build a [`Mesh`]
+/// directly.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polygon {
+    /// Corners in either winding order.
+    pub points: Vec<[f32; 2]>,
+    pub colour: Color,
+}
+
+impl Polygon {
+    pub fn new(points: Vec<[f32; 2]>, colour: impl Into<Color>) -> Self {
+        Self {
+            points,
+            colour: colour.into(),
+        }
+    }
+
+    /// Fan-triangulates the polygon into `n - 2` counter-clockwise triangles, flipping the fan
+    /// for clockwise input. Returns `None` for fewer than three points or a polygon with
+    /// (nearly) no area.
+    pub fn to_mesh(&self) -> Option<Mesh> {
+        if self.points.len() < 3 {
+            return None;
+        }
+        let area = self.signed_area();
+        let extent = self.extent();
+        if area.abs() <= extent * extent * MIN_AREA_FRACTION {
+            return None;
+        }
+        let clockwise = area < 0.0;
+        let indices = (1..self.points.len() as u32 - 1)
+            .flat_map(|i| {
+                if clockwise {
+                    [0, i + 1, i]
+                } else {
+                    [0, i, i + 1]
+                }
+            })
+            .collect();
+        Some(Mesh {
+            positions: self.points.clone(),
+            indices,
+        })
+    }
+
+    /// Shoelace area, positive when the points wind counter-clockwise.
+    fn signed_area(&self) -> f32 {
+        let n = self.points.len();
+        (0..n)
+            .map(|i| {
+                let [x0, y0] = self.points[i];
+                let [x1, y1] = self.points[(i + 1) % n];
+                x0 * y1 - x1 * y0
+            })
+            .sum::<f32>()
+            * 0.5
+    }
+
+    /// Larger side of the bounding box.
+    fn extent(&self) -> f32 {
+        let (mut min, mut max) = (self.points[0], self.points[0]);
+        for &[x, y] in &self.points {
+            min = [min[0].min(x), min[1].min(y)];
+            max = [max[0].max(x), max[1].max(y)];
+        }
+        (max[0] - min[0]).max(max[1] - min[1])
+    }
+}
+
+/// A circle outline: the band between two radii, built as a closed strip of quads.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ring {
+    pub center: [f32; 2],
+    pub inner_radius: f32,
+    pub outer_radius: f32,
+    pub colour: Color,
+    /// Number of quads around the circle.
+    pub segments: u32,
+}
+
+impl Ring {
+    pub fn new(
+        center: [f32; 2],
+        inner_radius: f32,
+        outer_radius: f32,
+        colour: impl Into<Color>,
+        segments: u32,
+    ) -> Self {
+        Self {
+            center,
+            inner_radius,
+            outer_radius,
+            colour: colour.into(),
+            segments,
+        }
+    }
+
+    /// Builds `2 * segments` vertices, alternating inner and outer, and two triangles per
+    /// segment. Returns `None` unless `0 <= inner_radius < outer_radius` and there are at least
+    /// three segments.
+    pub fn to_mesh(&self) -> Option<Mesh> {
+        if !(0.0 <= self.inner_radius && self.inner_radius < self.outer_radius) || self.segments < 3
+        {
+            return None;
+        }
+        let [cx, cy] = self.center;
+        let positions = (0..self.segments)
+            .flat_map(|i| {
+                let angle = i as f32 / self.segments as f32 * std::f32::consts::TAU;
+                let (sin, cos) = angle.sin_cos();
+                [
+                    [cx + cos * self.inner_radius, cy + sin * self.inner_radius],
+                    [cx + cos * self.outer_radius, cy + sin * self.outer_radius],
+                ]
+            })
+            .collect();
+        let indices = (0..self.segments)
+            .flat_map(|i| {
+                let inner = 2 * i;
+                let next = 2 * ((i + 1) % self.segments);
+                [inner, inner + 1, next + 1, inner, next + 1, next]
+            })
+            .collect();
+        Some(Mesh { positions, indices })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convex_pentagon_fans_into_three_triangles() {
+        let points = vec![[0.0, 0.0], [2.0, 0.0], [3.0, 1.0], [1.0, 2.0], [-1.0, 1.0]];
+        let mesh = Polygon::new(points, Color::RED).to_mesh().unwrap();
+        assert_eq!(mesh.triangle_count(), 3);
+        assert_eq!(mesh.indices, vec![0, 1, 2, 0, 2, 3, 0, 3, 4]);
+    }
+
+    #[test]
+    fn degenerate_polygons_are_skipped() {
+        assert!(Polygon::new(vec![[0.0, 0.0], [1.0, 0.0]], Color::RED)
+            .to_mesh()
+            .is_none());
+        let collinear = vec![[0.0, 0.0], [1.0, 0.0], [2.0, 0.0]];
+        assert!(Polygon::new(collinear, Color::RED).to_mesh().is_none());
+        let nearly = vec![[0.0, 0.0], [1.0, 0.0], [2.0, 1e-7]];
+        assert!(Polygon::new(nearly, Color::RED).to_mesh().is_none());
+    }
+
+    #[test]
+    fn clockwise_polygons_are_wound_counter_clockwise() {
+        let square = vec![[0.0, 0.0], [0.0, 1.0], [1.0, 1.0], [1.0, 0.0]];
+        let mesh = Polygon::new(square, Color::RED).to_mesh().unwrap();
+        assert_eq!(mesh.indices, vec![0, 2, 1, 0, 3, 2]);
+        for tri in mesh.indices.chunks(3) {
+            let [a, b, c] = [0, 1, 2].map(|k| mesh.positions[tri[k] as usize]);
+            let cross = (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0]);
+            assert!(cross > 0.0);
+        }
+    }
+
+    #[test]
+    fn ring_strip_spans_both_radii() {
+        let ring = Ring::new([1.0, 2.0], 3.0, 4.0, Color::WHITE, 16);
+        let mesh = ring.to_mesh().unwrap();
+        assert_eq!(mesh.positions.len(), 32);
+        assert_eq!(mesh.triangle_count(), 32);
+        for (i, [x, y]) in mesh.positions.iter().enumerate() {
+            let radius = ((x - 1.0).powi(2) + (y - 2.0).powi(2)).sqrt();
+            let expected = if i % 2 == 0 { 3.0 } else { 4.0 };
+            assert!((radius - expected).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn ring_rejects_inverted_radii() {
+        assert!(Ring::new([0.0, 0.0], 2.0, 1.0, Color::WHITE, 16)
+            .to_mesh()
+            .is_none());
+        assert!(Ring::new([0.0, 0.0], 1.0, 1.0, Color::WHITE, 16)
+            .to_mesh()
+            .is_none());
+        assert!(Ring::new([0.0, 0.0], 1.0, 2.0, Color::WHITE, 2)
+            .to_mesh()
+            .is_none());
+    }
+}
@@ -0,0 +1,131 @@
+/// One of the nine pieces of a [`NineSlice`], ready to be emitted as a textured quad.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SliceQuad {
+    /// Bottom-left corner in world units.
+    pub position: [f32; 2],
+    pub size: [f32; 2],
+    /// Texture sub-rect as `[x, y, w, h]` in UV space.
+    pub uv: [f32; 4],
+}
+
+/// A texture region split at its borders so it can be stretched without distorting the corners.
+///
+/// Corners keep their native size, edges stretch along one axis and the centre along both.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NineSlice {
+    /// Texture sub-rect as `[x, y, w, h]` in UV space, with y pointing down the image.
+    pub uv: [f32; 4],
+    /// Size of the region in texels, used to convert the insets into UV space.
+    pub texel_size: [f32; 2],
+    /// Border widths in texels as `[left, right, top, bottom]`.
+    pub insets: [f32; 4],
+}
+
+impl NineSlice {
+    pub fn new(uv: [f32; 4], texel_size: [f32; 2], insets: [f32; 4]) -> Self {
+        Self {
+            uv,
+            texel_size,
+            insets,
+        }
+    }
+
+    /// Splits a `size` panel with its bottom-left corner at `position` into nine quads, row by
+    /// row from the bottom-left.
+    ///
+    /// When the panel is narrower or shorter than the opposing insets combined, both insets on
+    /// that axis shrink proportionally and the middle pieces collapse to zero size, so no quad
+    /// is ever inverted.
+    pub fn quads(&self, position: [f32; 2], size: [f32; 2]) -> [SliceQuad; 9] {
+        let [left, right, top, bottom] = self.insets.map(|inset| inset.max(0.0));
+        let [x, y] = position;
+        let [width, height] = [size[0].max(0.0), size[1].max(0.0)];
+
+        let (left_w, right_w) = fit(left, right, width);
+        let (bottom_w, top_w) = fit(bottom, top, height);
+        let xs = [x, x + left_w, x + width - right_w, x + width];
+        let ys = [y, y + bottom_w, y + height - top_w, y + height];
+
+        let [u, v, uv_w, uv_h] = self.uv;
+        let (left_t, right_t) = fit(left, right, self.texel_size[0]);
+        let (bottom_t, top_t) = fit(bottom, top, self.texel_size[1]);
+        let to_u = uv_w / self.texel_size[0];
+        let to_v = uv_h / self.texel_size[1];
+        let us = [u, u + left_t * to_u, u + uv_w - right_t * to_u, u + uv_w];
+        // Rows run bottom to top in the world but top to bottom in the image.
+        let vs = [v + uv_h, v + uv_h - bottom_t * to_v, v + top_t * to_v, v];
+
+        let mut quads = [SliceQuad::default(); 9];
+        for row in 0..3 {
+            for col in 0..3 {
+                quads[row * 3 + col] = SliceQuad {
+                    position: [xs[col], ys[row]],
+                    size: [xs[col + 1] - xs[col], ys[row + 1] - ys[row]],
+                    uv: [
+                        us[col],
+                        vs[row + 1],
+                        us[col + 1] - us[col],
+                        vs[row] - vs[row + 1],
+                    ],
+                };
+            }
+        }
+        quads
+    }
+}
+
+/// Shrinks a pair of opposing insets proportionally so they fit within `extent`.
+fn fit(a: f32, b: f32, extent: f32) -> (f32, f32) {
+    let total = a + b;
+    if total > extent && total > 0.0 {
+        let scale = extent / total;
+        (a * scale, b * scale)
+    } else {
+        (a, b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: [f32; 4], expected: [f32; 4]) {
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-5, "{actual:?} != {expected:?}");
+        }
+    }
+
+    #[test]
+    fn corners_keep_native_size_while_centre_stretches() {
+        let slice = NineSlice::new([0.0, 0.0, 1.0, 1.0], [30.0, 30.0], [10.0; 4]);
+        let quads = slice.quads([5.0, 5.0], [100.0, 60.0]);
+
+        let bottom_left = quads[0];
+        assert_eq!(bottom_left.position, [5.0, 5.0]);
+        assert_eq!(bottom_left.size, [10.0, 10.0]);
+        assert_close(bottom_left.uv, [0.0, 2.0 / 3.0, 1.0 / 3.0, 1.0 / 3.0]);
+
+        let centre = quads[4];
+        assert_eq!(centre.position, [15.0, 15.0]);
+        assert_eq!(centre.size, [80.0, 40.0]);
+        assert_close(centre.uv, [1.0 / 3.0, 1.0 / 3.0, 1.0 / 3.0, 1.0 / 3.0]);
+
+        let top_right = quads[8];
+        assert_eq!(top_right.position, [95.0, 55.0]);
+        assert_eq!(top_right.size, [10.0, 10.0]);
+        assert_close(top_right.uv, [2.0 / 3.0, 0.0, 1.0 / 3.0, 1.0 / 3.0]);
+    }
+
+    #[test]
+    fn undersized_target_clamps_instead_of_inverting() {
+        let slice = NineSlice::new([0.0, 0.0, 1.0, 1.0], [30.0, 30.0], [10.0, 30.0, 10.0, 10.0]);
+        let quads = slice.quads([0.0, 0.0], [20.0, 10.0]);
+        for quad in quads {
+            assert!(quad.size[0] >= 0.0 && quad.size[1] >= 0.0, "{quad:?}");
+        }
+        assert_eq!(quads[0].size, [5.0, 5.0]);
+        assert_eq!(quads[1].size, [0.0, 5.0]);
+        assert_eq!(quads[2].size, [15.0, 5.0]);
+        assert_eq!(quads[4].size, [0.0, 0.0]);
+    }
+}
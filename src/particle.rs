@@ -0,0 +1,359 @@
+use std::f32::consts::{FRAC_PI_2, TAU};
+
+use crate::color::Color;
+
+/// Small xorshift generator, plenty for visual randomness and keeps emitters deterministic
+/// for a given seed.
+#[derive(Debug, Clone)]
+struct Rng(u32);
+
+impl Rng {
+    fn next_f32(&mut self) -> f32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 17;
+        self.0 ^= self.0 << 5;
+        (self.0 >> 8) as f32 / (1u32 << 24) as f32
+    }
+
+    fn range(&mut self, (min, max): (f32, f32)) -> f32 {
+        min + (max - min) * self.next_f32()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Particle {
+    pub position: [f32; 2],
+    pub velocity: [f32; 2],
+    /// Seconds since the particle was spawned.
+    pub age: f32,
+    /// Seconds the particle lives in total.
+    pub lifetime: f32,
+}
+
+impl Particle {
+    /// How far through its life the particle is, in 0..1.
+    pub fn progress(&self) -> f32 {
+        if self.lifetime > 0.0 {
+            (self.age / self.lifetime).clamp(0.0, 1.0)
+        } else {
+            1.0
+        }
+    }
+}
+
+/// How a [`ParticleEmitter`] spawns and moves its particles.
+///
+/// Ranges are `(min, max)` pairs that each particle picks uniformly from when it spawns.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmitterConfig {
+    /// Particles spawned per second while emitting.
+    pub rate: f32,
+    /// Seconds each particle lives.
+    pub lifetime: (f32, f32),
+    /// Centre of the launch cone in radians, counter-clockwise from +x.
+    pub direction: f32,
+    /// Full width of the launch cone in radians; `TAU` launches in every direction.
+    pub spread: f32,
+    /// Launch speed in world units per second.
+    pub speed: (f32, f32),
+    /// Acceleration applied to every live particle, in world units per second squared.
+    pub gravity: [f32; 2],
+    /// Colour at spawn and at the end of the lifetime, interpolated in between.
+    pub colour: (Color, Color),
+    /// Quad size at spawn and at the end of the lifetime, interpolated in between.
+    pub size: (f32, f32),
+    /// Most particles alive at once. Spawns beyond this are dropped.
+    pub max_particles: usize,
+}
+
+impl Default for EmitterConfig {
+    fn default() -> Self {
+        Self {
+            rate: 10.0,
+            lifetime: (1.0, 1.0),
+            direction: FRAC_PI_2,
+            spread: TAU,
+            speed: (0.0, 1.0),
+            gravity: [0.0, 0.0],
+            colour: (Color::WHITE, Color::TRANSPARENT),
+            size: (1.0, 1.0),
+            max_particles: 1000,
+        }
+    }
+}
+
+/// Spawns particles at a point and moves, ages and culls them on the CPU.
+///
+/// Storage for `max_particles` is allocated up front and reused, spawning never allocates.
+#[derive(Debug, Clone)]
+pub struct ParticleEmitter {
+    pub position: [f32; 2],
+    pub config: EmitterConfig,
+    particles: Vec<Particle>,
+    spawn_debt: f32,
+    emitting: bool,
+    rng: Rng,
+}
+
+impl ParticleEmitter {
+    pub fn new(position: [f32; 2], config: EmitterConfig) -> Self {
+        Self {
+            position,
+            particles: Vec::with_capacity(config.max_particles),
+            config,
+            spawn_debt: 0.0,
+            emitting: true,
+            rng: Rng(0x9e37_79b9),
+        }
+    }
+
+    /// Spawns up to `count` particles at once, even while stopped.
+    pub fn burst(&mut self, count: usize) {
+        let room = self
+            .config
+            .max_particles
+            .saturating_sub(self.particles.len());
+        for _ in 0..count.min(room) {
+            self.spawn();
+        }
+    }
+
+    /// Stops spawning at `rate`. Live particles keep moving until they expire.
+    pub fn stop(&mut self) {
+        self.emitting = false;
+        self.spawn_debt = 0.0;
+    }
+
+    /// Resumes spawning at `rate` after [`ParticleEmitter::stop`].
+    pub fn start(&mut self) {
+        self.emitting = true;
+    }
+
+    pub fn is_emitting(&self) -> bool {
+        self.emitting
+    }
+
+    /// Whether the emitter is stopped and every particle has expired, so it can be dropped.
+    pub fn is_done(&self) -> bool {
+        !self.emitting && self.particles.is_empty()
+    }
+
+    /// Moves and ages live particles by `dt` seconds, culls the expired ones, then spawns new
+    /// ones at `rate`. Fractional spawns carry over to the next call.
+    pub fn emit_and_update(&mut self, dt: f32) {
+        let [gx, gy] = self.config.gravity;
+        self.particles.retain_mut(|p| {
+            p.age += dt;
+            p.velocity[0] += gx * dt;
+            p.velocity[1] += gy * dt;
+            p.position[0] += p.velocity[0] * dt;
+            p.position[1] += p.velocity[1] * dt;
+            p.age < p.lifetime
+        });
+        if !self.emitting {
+            return;
+        }
+        self.spawn_debt += self.config.rate * dt;
+        let due = self.spawn_debt.floor();
+        if due >= 1.0 {
+            // Spawns that don't fit under the cap are dropped, not queued.
+            self.spawn_debt -= due;
+            self.burst(due as usize);
+        }
+    }
+
+    pub fn particles(&self) -> &[Particle] {
+        &self.particles
+    }
+
+    /// Colour of `particle` for its current age.
+    pub fn colour(&self, particle: &Particle) -> Color {
+        let (start, end) = self.config.colour;
+        start.lerp(end, particle.progress())
+    }
+
+    /// Quad size of `particle` for its current age.
+    pub fn size(&self, particle: &Particle) -> f32 {
+        let (start, end) = self.config.size;
+        start + (end - start) * particle.progress()
+    }
+
+    fn spawn(&mut self) {
+        if self.particles.len() >= self.config.max_particles {
+            return;
+        }
+        let half_spread = self.config.spread * 0.5;
+        let angle = self.config.direction + self.rng.range((-half_spread, half_spread));
+        let speed = self.rng.range(self.config.speed);
+        let (sin, cos) = angle.sin_cos();
+        let lifetime = self.rng.range(self.config.lifetime);
+        self.particles.push(Particle {
+            position: self.position,
+            velocity: [cos * speed, sin * speed],
+            age: 0.0,
+            lifetime,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(rate: f32, lifetime: f32) -> EmitterConfig {
+        EmitterConfig {
+            rate,
+            lifetime: (lifetime, lifetime),
+            ..EmitterConfig::default()
+        }
+    }
+
+    #[test]
+    fn burst_is_culled_after_lifetime() {
+        let mut emitter = ParticleEmitter::new([0.0, 0.0], config(0.0, 1.0));
+        emitter.burst(10);
+        emitter.emit_and_update(0.5);
+        assert_eq!(emitter.particles().len(), 10);
+        emitter.emit_and_update(0.5);
+        assert!(emitter.particles().is_empty());
+    }
+
+    #[test]
+    fn rate_carries_fractional_spawns() {
+        let mut emitter = ParticleEmitter::new([0.0, 0.0], config(10.0, 5.0));
+        for _ in 0..4 {
+            emitter.emit_and_update(0.25);
+        }
+        assert_eq!(emitter.particles().len(), 10);
+    }
+
+    #[test]
+    fn lifetimes_fall_within_range() {
+        let mut emitter = ParticleEmitter::new(
+            [0.0, 0.0],
+            EmitterConfig {
+                rate: 0.0,
+                lifetime: (0.5, 1.0),
+                ..EmitterConfig::default()
+            },
+        );
+        emitter.burst(50);
+        emitter.emit_and_update(0.45);
+        assert_eq!(emitter.particles().len(), 50);
+        emitter.emit_and_update(0.6);
+        assert!(emitter.particles().is_empty());
+    }
+
+    #[test]
+    fn particles_launch_within_cone() {
+        let mut emitter = ParticleEmitter::new(
+            [1.0, 1.0],
+            EmitterConfig {
+                rate: 0.0,
+                lifetime: (5.0, 5.0),
+                direction: 0.0,
+                spread: FRAC_PI_2,
+                speed: (1.0, 2.0),
+                ..EmitterConfig::default()
+            },
+        );
+        emitter.burst(50);
+        for p in emitter.particles() {
+            let [vx, vy] = p.velocity;
+            let speed = vx.hypot(vy);
+            assert!((1.0..=2.0).contains(&speed), "{speed}");
+            assert!(vy.atan2(vx).abs() <= FRAC_PI_2 * 0.5 + 1e-5);
+        }
+    }
+
+    #[test]
+    fn gravity_accelerates_particles() {
+        let mut emitter = ParticleEmitter::new(
+            [0.0, 0.0],
+            EmitterConfig {
+                rate: 0.0,
+                lifetime: (5.0, 5.0),
+                speed: (0.0, 0.0),
+                gravity: [0.0, -10.0],
+                ..EmitterConfig::default()
+            },
+        );
+        emitter.burst(1);
+        emitter.emit_and_update(0.5);
+        emitter.emit_and_update(0.5);
+        let p = emitter.particles()[0];
+        assert_eq!(p.velocity, [0.0, -10.0]);
+        assert_eq!(p.position, [0.0, -7.5]);
+    }
+
+    #[test]
+    fn colour_and_size_follow_age() {
+        let mut emitter = ParticleEmitter::new(
+            [0.0, 0.0],
+            EmitterConfig {
+                rate: 0.0,
+                lifetime: (2.0, 2.0),
+                colour: (Color::WHITE, Color::BLACK),
+                size: (4.0, 2.0),
+                ..EmitterConfig::default()
+            },
+        );
+        emitter.burst(1);
+        emitter.emit_and_update(1.0);
+        let p = emitter.particles()[0];
+        assert_eq!(emitter.size(&p), 3.0);
+        assert_eq!(emitter.colour(&p), Color::WHITE.lerp(Color::BLACK, 0.5));
+    }
+
+    #[test]
+    fn live_particles_are_capped_without_reallocating() {
+        let mut emitter = ParticleEmitter::new(
+            [0.0, 0.0],
+            EmitterConfig {
+                rate: 100.0,
+                max_particles: 5,
+                ..config(0.0, 1.0)
+            },
+        );
+        let capacity = emitter.particles.capacity();
+        emitter.burst(10);
+        assert_eq!(emitter.particles().len(), 5);
+        for _ in 0..20 {
+            emitter.emit_and_update(0.1);
+            assert!(emitter.particles().len() <= 5);
+        }
+        assert_eq!(emitter.particles.capacity(), capacity);
+    }
+
+    #[test]
+    fn huge_spawn_counts_stop_at_the_cap() {
+        let mut emitter = ParticleEmitter::new(
+            [0.0, 0.0],
+            EmitterConfig {
+                rate: 1e12,
+                max_particles: 5,
+                ..config(0.0, 1.0)
+            },
+        );
+        emitter.emit_and_update(1.0);
+        assert_eq!(emitter.particles().len(), 5);
+        emitter.burst(usize::MAX);
+        assert_eq!(emitter.particles().len(), 5);
+    }
+
+    #[test]
+    fn stopped_emitter_drains() {
+        let mut emitter = ParticleEmitter::new([0.0, 0.0], config(10.0, 1.0));
+        emitter.emit_and_update(0.5);
+        assert_eq!(emitter.particles().len(), 5);
+        emitter.stop();
+        emitter.emit_and_update(0.5);
+        assert_eq!(emitter.particles().len(), 5);
+        assert!(!emitter.is_done());
+        emitter.emit_and_update(1.0);
+        assert!(emitter.is_done());
+        emitter.start();
+        emitter.emit_and_update(0.5);
+        assert_eq!(emitter.particles().len(), 5);
+    }
+}
@@ -0,0 +1,136 @@
+use crate::collision::Aabb;
+
+/// The topmost item whose bounds contain `point`.
+///
+/// Items are given in draw order, so later ones are on top and win ties.
+pub fn pick<K>(point: [f32; 2], items: impl IntoIterator<Item = (K, Aabb)>) -> Option<K> {
+    items
+        .into_iter()
+        .filter(|(_, bounds)| bounds.contains_point(point))
+        .last()
+        .map(|(key, _)| key)
+}
+
+/// The exact outline of a drawable for hit testing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PickShape {
+    /// A rectangle rotated by `rotation` radians, counter-clockwise, around its centre.
+    Rect {
+        center: [f32; 2],
+        half_extents: [f32; 2],
+        rotation: f32,
+    },
+    Circle {
+        center: [f32; 2],
+        radius: f32,
+    },
+}
+
+impl PickShape {
+    /// Inclusive of the outline, like [`Aabb::contains_point`].
+    pub fn contains_point(&self, point: [f32; 2]) -> bool {
+        match *self {
+            PickShape::Rect {
+                center,
+                half_extents,
+                rotation,
+            } => {
+                let (dx, dy) = (point[0] - center[0], point[1] - center[1]);
+                // Rotate the point into the rectangle's frame rather than the other way around.
+                let (sin, cos) = rotation.sin_cos();
+                let x = dx * cos + dy * sin;
+                let y = -dx * sin + dy * cos;
+                x.abs() <= half_extents[0] && y.abs() <= half_extents[1]
+            }
+            PickShape::Circle { center, radius } => {
+                let (dx, dy) = (point[0] - center[0], point[1] - center[1]);
+                dx * dx + dy * dy <= radius * radius
+            }
+        }
+    }
+}
+
+/// The topmost item whose shape contains `point`: the highest layer, and within a layer the
+/// item given last. Items are `(key, layer, shape)` in the order they were added. O(items).
+pub fn pick_layered<K>(
+    point: [f32; 2],
+    items: impl IntoIterator<Item = (K, i32, PickShape)>,
+) -> Option<K> {
+    items
+        .into_iter()
+        .filter(|(_, _, shape)| shape.contains_point(point))
+        .fold(None, |top: Option<(K, i32)>, (key, layer, _)| match top {
+            Some((_, top_layer)) if top_layer > layer => top,
+            _ => Some((key, layer)),
+        })
+        .map(|(key, _)| key)
+}
+
+/// Every item whose shape contains `point`, topmost first, ordered as for [`pick_layered`].
+pub fn pick_all<K>(
+    point: [f32; 2],
+    items: impl IntoIterator<Item = (K, i32, PickShape)>,
+) -> Vec<K> {
+    let mut hits: Vec<(usize, i32, K)> = items
+        .into_iter()
+        .enumerate()
+        .filter(|(_, (_, _, shape))| shape.contains_point(point))
+        .map(|(order, (key, layer, _))| (order, layer, key))
+        .collect();
+    hits.sort_by_key(|&(order, layer, _)| std::cmp::Reverse((layer, order)));
+    hits.into_iter().map(|(_, _, key)| key).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn front_square_wins_overlap() {
+        let squares = [
+            ("back", Aabb::from_position_size([0.0, 0.0], [2.0, 2.0])),
+            ("front", Aabb::from_position_size([1.0, 1.0], [2.0, 2.0])),
+        ];
+        assert_eq!(pick([1.5, 1.5], squares), Some("front"));
+        assert_eq!(pick([0.5, 0.5], squares), Some("back"));
+        assert_eq!(pick([5.0, 5.0], squares), None);
+    }
+
+    #[test]
+    fn rotated_rect_excludes_its_aabb_corners() {
+        let diamond = PickShape::Rect {
+            center: [0.0, 0.0],
+            half_extents: [1.0, 1.0],
+            rotation: std::f32::consts::FRAC_PI_4,
+        };
+        assert!(diamond.contains_point([0.0, 1.4]));
+        assert!(!diamond.contains_point([0.9, 0.9]));
+        let circle = PickShape::Circle {
+            center: [1.0, 1.0],
+            radius: 1.0,
+        };
+        assert!(circle.contains_point([1.0, 2.0]));
+        assert!(!circle.contains_point([1.8, 1.8]));
+    }
+
+    #[test]
+    fn higher_layers_win_then_latest_added() {
+        let disc = |radius| PickShape::Circle {
+            center: [0.0, 0.0],
+            radius,
+        };
+        let items = [
+            ("raised", 1, disc(1.0)),
+            ("ground", 0, disc(2.0)),
+            ("raised_later", 1, disc(1.0)),
+            ("ground_later", 0, disc(2.0)),
+        ];
+        assert_eq!(pick_layered([0.0, 0.0], items), Some("raised_later"));
+        assert_eq!(pick_layered([1.5, 0.0], items), Some("ground_later"));
+        assert_eq!(pick_layered([3.0, 0.0], items), None);
+        assert_eq!(
+            pick_all([0.0, 0.0], items),
+            vec!["raised_later", "raised", "ground_later", "ground"]
+        );
+    }
+}
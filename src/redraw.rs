@@ -0,0 +1,145 @@
+use crate::collision::Aabb;
+use crate::entity::Changes;
+
+/// When the window should be redrawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RedrawMode {
+    /// Redraw every frame, for scenes that animate.
+    #[default]
+    Continuous,
+    /// Redraw only after something visible changed, so static scenes stay idle.
+    OnDemand,
+}
+
+/// Tracks whether anything visible changed since the last drawn frame.
+///
+/// Feed it the scene's changes, the camera's view and the window size each frame, ask
+/// [`RedrawState::needs_redraw`] whether to request a redraw, and call
+/// [`RedrawState::frame_drawn`] once the frame is presented.
+#[derive(Debug, Clone)]
+pub struct RedrawState {
+    mode: RedrawMode,
+    dirty: bool,
+    view: Option<Aabb>,
+    size: Option<[u32; 2]>,
+}
+
+impl RedrawState {
+    /// The first frame always needs drawing.
+    pub fn new(mode: RedrawMode) -> Self {
+        Self {
+            mode,
+            dirty: true,
+            view: None,
+            size: None,
+        }
+    }
+
+    pub fn mode(&self) -> RedrawMode {
+        self.mode
+    }
+
+    pub fn set_mode(&mut self, mode: RedrawMode) {
+        self.mode = mode;
+    }
+
+    /// Forces the next frame to be drawn, for changes the tracker can't see.
+    pub fn request_redraw(&mut self) {
+        self.dirty = true;
+    }
+
+    /// Marks the frame dirty when entities were added, removed or changed.
+    pub fn record_changes(&mut self, changes: &Changes) {
+        if *changes != Changes::None {
+            self.dirty = true;
+        }
+    }
+
+    /// Marks the frame dirty when the camera's visible area moved or zoomed since last time.
+    pub fn set_view(&mut self, view: Aabb) {
+        if self.view != Some(view) {
+            self.view = Some(view);
+            self.dirty = true;
+        }
+    }
+
+    /// Marks the frame dirty when the window size changed since last time.
+    pub fn set_size(&mut self, size: [u32; 2]) {
+        if self.size != Some(size) {
+            self.size = Some(size);
+            self.dirty = true;
+        }
+    }
+
+    /// Whether a redraw should be requested this frame. Always true in
+    /// [`RedrawMode::Continuous`].
+    pub fn needs_redraw(&self) -> bool {
+        self.mode == RedrawMode::Continuous || self.dirty
+    }
+
+    /// Clears the pending changes once a frame has been drawn.
+    pub fn frame_drawn(&mut self) {
+        self.dirty = false;
+    }
+}
+
+impl Default for RedrawState {
+    fn default() -> Self {
+        Self::new(RedrawMode::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::Entities;
+
+    fn drawn_once() -> RedrawState {
+        let mut state = RedrawState::new(RedrawMode::OnDemand);
+        state.set_view(Aabb::new([-1.0, -1.0], [1.0, 1.0]));
+        state.set_size([800, 600]);
+        assert!(state.needs_redraw());
+        state.frame_drawn();
+        state
+    }
+
+    #[test]
+    fn on_demand_without_changes_stays_idle() {
+        let mut state = drawn_once();
+        state.record_changes(&Changes::None);
+        state.set_view(Aabb::new([-1.0, -1.0], [1.0, 1.0]));
+        state.set_size([800, 600]);
+        assert!(!state.needs_redraw());
+    }
+
+    #[test]
+    fn adding_geometry_needs_redraw() {
+        let mut state = drawn_once();
+        let mut entities = Entities::new();
+        entities.spawn([0.0f32, 0.0]);
+        state.record_changes(&entities.take_changes());
+        assert!(state.needs_redraw());
+        state.frame_drawn();
+        state.record_changes(&entities.take_changes());
+        assert!(!state.needs_redraw());
+    }
+
+    #[test]
+    fn camera_moves_and_resizes_need_redraw() {
+        let mut state = drawn_once();
+        state.set_view(Aabb::new([0.0, -1.0], [2.0, 1.0]));
+        assert!(state.needs_redraw());
+        state.frame_drawn();
+        state.set_size([1024, 768]);
+        assert!(state.needs_redraw());
+    }
+
+    #[test]
+    fn continuous_always_redraws() {
+        let mut state = drawn_once();
+        state.set_mode(RedrawMode::Continuous);
+        assert!(state.needs_redraw());
+        state.frame_drawn();
+        assert!(state.needs_redraw());
+    }
+}
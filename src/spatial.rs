@@ -0,0 +1,255 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use crate::collision::Aabb;
+
+/// Most cells a single item is indexed into. Bigger items (a backdrop, a level boundary) go in
+/// a list every query checks directly, so inserting one doesn't fill millions of cells.
+const MAX_CELLS_PER_ITEM: i64 = 1024;
+
+/// A uniform grid indexing boxes by the cells they cover, for region and point queries that
+/// don't have to scan every item.
+#[derive(Debug, Clone)]
+pub struct SpatialHash<K> {
+    cell_size: f32,
+    cells: HashMap<(i32, i32), Vec<K>>,
+    bounds: HashMap<K, Aabb>,
+    /// Items covering more than [`MAX_CELLS_PER_ITEM`] cells, kept out of `cells`.
+    oversized: HashSet<K>,
+    /// Smallest cell range covering every insert since the last clear. Queries are clamped to
+    /// it so a huge region doesn't walk millions of empty cells.
+    occupied: Option<CellRange>,
+}
+
+/// Inclusive range of cells, as `(min, max)` corners.
+type CellRange = ((i32, i32), (i32, i32));
+
+impl<K: Copy + Eq + Hash> SpatialHash<K> {
+    pub fn new(cell_size: f32) -> Self {
+        assert!(cell_size > 0.0, "cell size must be positive");
+        Self {
+            cell_size,
+            cells: HashMap::new(),
+            bounds: HashMap::new(),
+            oversized: HashSet::new(),
+            occupied: None,
+        }
+    }
+
+    /// A grid sized for items of roughly `average_size`, so most of them cover a cell or two.
+    pub fn for_average_size(average_size: f32) -> Self {
+        Self::new(average_size * 2.0)
+    }
+
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    pub fn len(&self) -> usize {
+        self.bounds.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bounds.is_empty()
+    }
+
+    /// Adds `key` with the given bounds, or moves it if it's already present.
+    pub fn insert(&mut self, key: K, bounds: Aabb) {
+        self.remove(key);
+        self.bounds.insert(key, bounds);
+        let range = self.cell_range(&bounds);
+        if cell_count(range) > MAX_CELLS_PER_ITEM {
+            self.oversized.insert(key);
+            return;
+        }
+        for cell in cells_in(range) {
+            self.cells.entry(cell).or_default().push(key);
+        }
+        let ((x0, y0), (x1, y1)) = range;
+        self.occupied = Some(match self.occupied {
+            Some(((ox0, oy0), (ox1, oy1))) => {
+                ((ox0.min(x0), oy0.min(y0)), (ox1.max(x1), oy1.max(y1)))
+            }
+            None => range,
+        });
+    }
+
+    /// Removes `key`, returning its bounds if it was present.
+    pub fn remove(&mut self, key: K) -> Option<Aabb> {
+        let bounds = self.bounds.remove(&key)?;
+        if self.oversized.remove(&key) {
+            return Some(bounds);
+        }
+        for cell in cells_in(self.cell_range(&bounds)) {
+            if let Some(keys) = self.cells.get_mut(&cell) {
+                keys.retain(|k| *k != key);
+                if keys.is_empty() {
+                    self.cells.remove(&cell);
+                }
+            }
+        }
+        Some(bounds)
+    }
+
+    pub fn bounds(&self, key: K) -> Option<&Aabb> {
+        self.bounds.get(&key)
+    }
+
+    /// Every key whose bounds intersect `region`, in no particular order.
+    ///
+    /// Costs at most one lookup per occupied cell plus a check of each oversized item, however
+    /// large `region` is.
+    pub fn query_region(&self, region: &Aabb) -> Vec<K> {
+        let mut seen = HashSet::new();
+        let mut hits: Vec<K> = self
+            .oversized
+            .iter()
+            .copied()
+            .filter(|key| self.bounds[key].intersects(region))
+            .collect();
+        let Some(range) = self.clamp_to_occupied(self.cell_range(region)) else {
+            return hits;
+        };
+        let mut visit = |keys: &Vec<K>| {
+            for key in keys {
+                if seen.insert(*key) && self.bounds[key].intersects(region) {
+                    hits.push(*key);
+                }
+            }
+        };
+        let ((x0, y0), (x1, y1)) = range;
+        if cell_count(range) > self.cells.len() as i64 {
+            // Fewer occupied cells than cells in range, cheaper to scan the occupied ones.
+            for (&(x, y), keys) in &self.cells {
+                if (x0..=x1).contains(&x) && (y0..=y1).contains(&y) {
+                    visit(keys);
+                }
+            }
+        } else {
+            for cell in cells_in(range) {
+                if let Some(keys) = self.cells.get(&cell) {
+                    visit(keys);
+                }
+            }
+        }
+        hits
+    }
+
+    /// Every key whose bounds contain `point`, in no particular order.
+    pub fn query_point(&self, point: [f32; 2]) -> Vec<K> {
+        let cell = self.cell_of(point);
+        self.cells
+            .get(&cell)
+            .into_iter()
+            .flatten()
+            .chain(&self.oversized)
+            .copied()
+            .filter(|key| self.bounds[key].contains_point(point))
+            .collect()
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.bounds.clear();
+        self.oversized.clear();
+        self.occupied = None;
+    }
+
+    fn cell_of(&self, p: [f32; 2]) -> (i32, i32) {
+        (
+            (p[0] / self.cell_size).floor() as i32,
+            (p[1] / self.cell_size).floor() as i32,
+        )
+    }
+
+    fn cell_range(&self, bounds: &Aabb) -> CellRange {
+        (self.cell_of(bounds.min), self.cell_of(bounds.max))
+    }
+
+    /// The part of `range` inside the occupied cells, or `None` if they don't overlap.
+    fn clamp_to_occupied(&self, range: CellRange) -> Option<CellRange> {
+        let ((x0, y0), (x1, y1)) = range;
+        let ((ox0, oy0), (ox1, oy1)) = self.occupied?;
+        let clamped = ((x0.max(ox0), y0.max(oy0)), (x1.min(ox1), y1.min(oy1)));
+        let ((cx0, cy0), (cx1, cy1)) = clamped;
+        (cx0 <= cx1 && cy0 <= cy1).then_some(clamped)
+    }
+}
+
+fn cell_count(((x0, y0), (x1, y1)): CellRange) -> i64 {
+    (x1 as i64 - x0 as i64 + 1) * (y1 as i64 - y0 as i64 + 1)
+}
+
+fn cells_in(((x0, y0), (x1, y1)): CellRange) -> impl Iterator<Item = (i32, i32)> {
+    (x0..=x1).flat_map(move |x| (y0..=y1).map(move |y| (x, y)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(x: f32, y: f32, size: f32) -> Aabb {
+        Aabb::from_position_size([x, y], [size, size])
+    }
+
+    fn sorted(mut keys: Vec<u32>) -> Vec<u32> {
+        keys.sort_unstable();
+        keys
+    }
+
+    #[test]
+    fn region_query_finds_items_across_cells() {
+        let mut hash = SpatialHash::new(10.0);
+        hash.insert(1, square(0.0, 0.0, 5.0));
+        hash.insert(2, square(8.0, 8.0, 5.0));
+        hash.insert(3, square(50.0, 50.0, 5.0));
+        assert_eq!(
+            sorted(hash.query_region(&square(4.0, 4.0, 5.0))),
+            vec![1, 2]
+        );
+        assert_eq!(hash.query_point([9.0, 9.0]), vec![2]);
+        assert!(hash.query_point([30.0, 30.0]).is_empty());
+    }
+
+    #[test]
+    fn moving_and_removing_update_the_index() {
+        let mut hash = SpatialHash::new(10.0);
+        hash.insert(1, square(0.0, 0.0, 5.0));
+        hash.insert(1, square(100.0, 100.0, 5.0));
+        assert_eq!(hash.len(), 1);
+        assert!(hash.query_region(&square(0.0, 0.0, 5.0)).is_empty());
+        assert_eq!(hash.query_point([102.0, 102.0]), vec![1]);
+        assert_eq!(hash.remove(1), Some(square(100.0, 100.0, 5.0)));
+        assert!(hash.is_empty());
+        assert!(hash.query_point([102.0, 102.0]).is_empty());
+    }
+
+    #[test]
+    fn huge_region_only_visits_occupied_cells() {
+        let mut hash = SpatialHash::new(1.0);
+        assert!(hash.query_region(&square(-1e30, -1e30, 2e30)).is_empty());
+        hash.insert(1, square(0.0, 0.0, 0.5));
+        hash.insert(2, square(-40.0, 70.0, 0.5));
+        let everything = Aabb::new([f32::MIN, f32::MIN], [f32::MAX, f32::MAX]);
+        assert_eq!(sorted(hash.query_region(&everything)), vec![1, 2]);
+        hash.clear();
+        assert!(hash.query_region(&everything).is_empty());
+    }
+
+    #[test]
+    fn huge_items_skip_the_grid_but_are_still_found() {
+        let mut hash = SpatialHash::for_average_size(10.0);
+        hash.insert(1, square(-5e5, -5e5, 1e6));
+        hash.insert(2, square(3.0, 3.0, 5.0));
+        assert!(hash.cells.len() <= 4);
+        assert_eq!(
+            sorted(hash.query_region(&square(0.0, 0.0, 5.0))),
+            vec![1, 2]
+        );
+        assert_eq!(hash.query_region(&square(1e5, 1e5, 1.0)), vec![1]);
+        assert!(hash.query_region(&square(1e6, 1e6, 1.0)).is_empty());
+        assert_eq!(sorted(hash.query_point([4.0, 4.0])), vec![1, 2]);
+        assert_eq!(hash.remove(1), Some(square(-5e5, -5e5, 1e6)));
+        assert_eq!(hash.query_point([4.0, 4.0]), vec![2]);
+    }
+}
@@ -0,0 +1,81 @@
+/// The whole texture as a `[x, y, w, h]` UV rect.
+pub const FULL_UV_RECT: [f32; 4] = [0.0, 0.0, 1.0, 1.0];
+
+/// Per-vertex UVs for a quad showing `uv_rect` (`[x, y, w, h]` in normalized texture
+/// coordinates, y pointing down the image).
+///
+/// Ordered bottom-left, bottom-right, top-right, top-left, matching [`Sprite::corners`].
+pub fn quad_uvs(uv_rect: [f32; 4]) -> [[f32; 2]; 4] {
+    let [u, v, w, h] = uv_rect;
+    [[u, v + h], [u + w, v + h], [u + w, v], [u, v]]
+}
+
+/// A textured quad showing a region of its texture, such as one frame of a sprite sheet.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sprite {
+    /// Bottom-left corner in world units.
+    pub position: [f32; 2],
+    pub size: [f32; 2],
+    /// Region of the texture to show as `[x, y, w, h]`, the whole texture by default.
+    pub uv_rect: [f32; 4],
+}
+
+impl Sprite {
+    pub fn new(position: [f32; 2], size: [f32; 2]) -> Self {
+        Self {
+            position,
+            size,
+            uv_rect: FULL_UV_RECT,
+        }
+    }
+
+    pub fn with_uv_rect(mut self, uv_rect: [f32; 4]) -> Self {
+        self.uv_rect = uv_rect;
+        self
+    }
+
+    /// Bottom-left, bottom-right, top-right and top-left corners.
+    pub fn corners(&self) -> [[f32; 2]; 4] {
+        let [x, y] = self.position;
+        let [w, h] = self.size;
+        [[x, y], [x + w, y], [x + w, y + h], [x, y + h]]
+    }
+
+    /// UVs for each of [`Sprite::corners`].
+    pub fn uvs(&self) -> [[f32; 2]; 4] {
+        quad_uvs(self.uv_rect)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uvs_default_to_whole_texture() {
+        let sprite = Sprite::new([0.0, 0.0], [1.0, 1.0]);
+        assert_eq!(
+            sprite.uvs(),
+            [[0.0, 1.0], [1.0, 1.0], [1.0, 0.0], [0.0, 0.0]]
+        );
+    }
+
+    #[test]
+    fn uvs_match_sub_rect_corners() {
+        let sprite = Sprite::new([2.0, 3.0], [4.0, 4.0]).with_uv_rect([0.25, 0.5, 0.25, 0.5]);
+        // The bottom-left corner of the quad shows the bottom-left of the region.
+        assert_eq!(
+            sprite
+                .corners()
+                .into_iter()
+                .zip(sprite.uvs())
+                .collect::<Vec<_>>(),
+            vec![
+                ([2.0, 3.0], [0.25, 1.0]),
+                ([6.0, 3.0], [0.5, 1.0]),
+                ([6.0, 7.0], [0.5, 0.5]),
+                ([2.0, 7.0], [0.25, 0.5]),
+            ]
+        );
+    }
+}
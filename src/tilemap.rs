@@ -0,0 +1,329 @@
+use crate::collision::Aabb;
+use crate::sprite::quad_uvs;
+
+/// Tile index meaning "no tile here".
+pub const EMPTY_TILE: u32 = u32::MAX;
+
+/// Tiles per chunk side. Chunks are the unit of culling and of vertex storage.
+const CHUNK_SIZE: u32 = 16;
+
+/// Pre-built quads for one square block of tiles.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TileChunk {
+    bounds: Aabb,
+    positions: Vec<[f32; 2]>,
+    uvs: Vec<[f32; 2]>,
+    indices: Vec<u32>,
+    /// Quad slot of each tile in the chunk, row-major, `None` until the tile is first set.
+    quads: Vec<Option<u32>>,
+}
+
+impl TileChunk {
+    /// Bounds in map-local coordinates.
+    pub fn bounds(&self) -> &Aabb {
+        &self.bounds
+    }
+
+    pub fn positions(&self) -> &[[f32; 2]] {
+        &self.positions
+    }
+
+    pub fn uvs(&self) -> &[[f32; 2]] {
+        &self.uvs
+    }
+
+    /// Six indices per quad, counter-clockwise.
+    pub fn indices(&self) -> &[u32] {
+        &self.indices
+    }
+}
+
+/// A grid of tiles from one atlas, stored as chunks of pre-built quads.
+///
+/// Row 0 is the top row, as in map files. Vertices are in map-local coordinates with the
+/// bottom-left corner of the map at the origin; draw them with `offset` as the model offset.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TileMap {
+    pub offset: [f32; 2],
+    width: u32,
+    height: u32,
+    tile_size: f32,
+    /// Atlas columns and rows. Tile `i` is at column `i % columns`, row `i / columns`.
+    atlas: [u32; 2],
+    tiles: Vec<u32>,
+    chunks: Vec<TileChunk>,
+}
+
+impl TileMap {
+    /// An empty `width` by `height` map.
+    pub fn new(width: u32, height: u32, tile_size: f32, atlas: [u32; 2]) -> Self {
+        assert!(
+            atlas[0] > 0 && atlas[1] > 0,
+            "atlas needs at least one tile"
+        );
+        let chunks_x = width.div_ceil(CHUNK_SIZE);
+        let chunks_y = height.div_ceil(CHUNK_SIZE);
+        let chunks = (0..chunks_y)
+            .flat_map(|cy| (0..chunks_x).map(move |cx| (cx, cy)))
+            .map(|(cx, cy)| {
+                let x0 = cx * CHUNK_SIZE;
+                let x1 = (x0 + CHUNK_SIZE).min(width);
+                let y0 = cy * CHUNK_SIZE;
+                let y1 = (y0 + CHUNK_SIZE).min(height);
+                TileChunk {
+                    bounds: Aabb::new(
+                        [x0 as f32 * tile_size, (height - y1) as f32 * tile_size],
+                        [x1 as f32 * tile_size, (height - y0) as f32 * tile_size],
+                    ),
+                    positions: Vec::new(),
+                    uvs: Vec::new(),
+                    indices: Vec::new(),
+                    quads: vec![None; (CHUNK_SIZE * CHUNK_SIZE) as usize],
+                }
+            })
+            .collect();
+        Self {
+            offset: [0.0, 0.0],
+            width,
+            height,
+            tile_size,
+            atlas,
+            tiles: vec![EMPTY_TILE; (width * height) as usize],
+            chunks,
+        }
+    }
+
+    /// Parses a map from comma-separated tile indices, one row per line, `-1` for no tile.
+    pub fn from_csv(csv: &str, tile_size: f32, atlas: [u32; 2]) -> Result<Self, ParseTileMapError> {
+        let mut rows = Vec::new();
+        for (line, text) in csv
+            .lines()
+            .enumerate()
+            .filter(|(_, l)| !l.trim().is_empty())
+        {
+            let row = text
+                .split(',')
+                .enumerate()
+                .map(|(column, cell)| match cell.trim() {
+                    "-1" => Ok(EMPTY_TILE),
+                    cell => cell.parse().map_err(|_| ParseTileMapError::InvalidIndex {
+                        line: line + 1,
+                        column: column + 1,
+                    }),
+                })
+                .collect::<Result<Vec<u32>, _>>()?;
+            if rows
+                .first()
+                .is_some_and(|first: &Vec<u32>| first.len() != row.len())
+            {
+                return Err(ParseTileMapError::RaggedRow { line: line + 1 });
+            }
+            rows.push(row);
+        }
+        let width = rows.first().ok_or(ParseTileMapError::Empty)?.len() as u32;
+        let mut map = Self::new(width, rows.len() as u32, tile_size, atlas);
+        for (y, row) in rows.into_iter().enumerate() {
+            for (x, index) in row.into_iter().enumerate() {
+                map.set_tile(x as u32, y as u32, index);
+            }
+        }
+        Ok(map)
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn tile_size(&self) -> f32 {
+        self.tile_size
+    }
+
+    /// The tile at column `x`, row `y`, or `None` when empty or outside the map.
+    pub fn tile(&self, x: u32, y: u32) -> Option<u32> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        Some(self.tiles[(y * self.width + x) as usize]).filter(|&index| index != EMPTY_TILE)
+    }
+
+    /// Changes one tile, patching only that tile's quad. Clearing a tile with [`EMPTY_TILE`]
+    /// collapses its quad to zero area so no other vertices move.
+    pub fn set_tile(&mut self, x: u32, y: u32, index: u32) {
+        assert!(x < self.width && y < self.height, "tile outside the map");
+        self.tiles[(y * self.width + x) as usize] = index;
+
+        let chunks_x = self.width.div_ceil(CHUNK_SIZE);
+        let chunk_index = (y / CHUNK_SIZE * chunks_x + x / CHUNK_SIZE) as usize;
+        let local = ((y % CHUNK_SIZE) * CHUNK_SIZE + x % CHUNK_SIZE) as usize;
+        let corners = self.tile_corners(x, y, index);
+        let uvs = self.tile_uvs(index);
+        let chunk = &mut self.chunks[chunk_index];
+        let quad = match chunk.quads[local] {
+            Some(quad) => quad,
+            None if index == EMPTY_TILE => return,
+            None => {
+                let quad = (chunk.positions.len() / 4) as u32;
+                let base = quad * 4;
+                chunk.positions.extend([[0.0; 2]; 4]);
+                chunk.uvs.extend([[0.0; 2]; 4]);
+                chunk
+                    .indices
+                    .extend([base, base + 1, base + 2, base, base + 2, base + 3]);
+                chunk.quads[local] = Some(quad);
+                quad
+            }
+        };
+        let range = quad as usize * 4..quad as usize * 4 + 4;
+        chunk.positions[range.clone()].copy_from_slice(&corners);
+        chunk.uvs[range].copy_from_slice(&uvs);
+    }
+
+    /// Chunks whose bounds, moved by `offset`, overlap `view`.
+    pub fn visible_chunks<'a>(&'a self, view: &'a Aabb) -> impl Iterator<Item = &'a TileChunk> {
+        let [ox, oy] = self.offset;
+        self.chunks.iter().filter(move |chunk| {
+            let bounds = chunk.bounds;
+            Aabb::new(
+                [bounds.min[0] + ox, bounds.min[1] + oy],
+                [bounds.max[0] + ox, bounds.max[1] + oy],
+            )
+            .intersects(view)
+        })
+    }
+
+    /// Every chunk, including ones with no tiles.
+    pub fn chunks(&self) -> &[TileChunk] {
+        &self.chunks
+    }
+
+    /// Bottom-left, bottom-right, top-right and top-left corners, or four copies of the
+    /// bottom-left one for an empty tile.
+    fn tile_corners(&self, x: u32, y: u32, index: u32) -> [[f32; 2]; 4] {
+        let size = self.tile_size;
+        let left = x as f32 * size;
+        let bottom = (self.height - 1 - y) as f32 * size;
+        if index == EMPTY_TILE {
+            return [[left, bottom]; 4];
+        }
+        [
+            [left, bottom],
+            [left + size, bottom],
+            [left + size, bottom + size],
+            [left, bottom + size],
+        ]
+    }
+
+    /// UVs matching [`TileMap::tile_corners`], with v pointing down the atlas.
+    fn tile_uvs(&self, index: u32) -> [[f32; 2]; 4] {
+        if index == EMPTY_TILE {
+            return [[0.0; 2]; 4];
+        }
+        let [columns, rows] = self.atlas;
+        let (w, h) = (1.0 / columns as f32, 1.0 / rows as f32);
+        let u = (index % columns) as f32 * w;
+        let v = (index / columns % rows) as f32 * h;
+        quad_uvs([u, v, w, h])
+    }
+}
+
+/// Why a tile map file could not be parsed. Lines and columns count from 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseTileMapError {
+    Empty,
+    InvalidIndex { line: usize, column: usize },
+    RaggedRow { line: usize },
+}
+
+impl std::fmt::Display for ParseTileMapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "tile map has no rows"),
+            Self::InvalidIndex { line, column } => {
+                write!(f, "invalid tile index at line {line}, column {column}")
+            }
+            Self::RaggedRow { line } => {
+                write!(
+                    f,
+                    "line {line} has a different number of tiles than the first row"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseTileMapError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_non_empty_tiles_get_quads() {
+        let map = TileMap::from_csv("0,-1,5\n-1,-1,2\n", 2.0, [4, 4]).unwrap();
+        assert_eq!((map.width(), map.height()), (3, 2));
+        assert_eq!(map.tile(2, 0), Some(5));
+        assert_eq!(map.tile(1, 0), None);
+        let chunk = &map.chunks()[0];
+        assert_eq!(chunk.positions().len(), 12);
+        assert_eq!(chunk.indices().len(), 18);
+
+        // Tile 5 sits in the top row, third column, and in the atlas' second row and column.
+        assert_eq!(chunk.positions()[4], [4.0, 2.0]);
+        assert_eq!(chunk.positions()[6], [6.0, 4.0]);
+        assert_eq!(chunk.uvs()[7], [0.25, 0.25]);
+        assert_eq!(chunk.uvs()[5], [0.5, 0.5]);
+    }
+
+    #[test]
+    fn set_tile_patches_in_place() {
+        let mut map = TileMap::new(4, 4, 1.0, [2, 2]);
+        map.set_tile(1, 1, 0);
+        map.set_tile(2, 1, 1);
+        let before = map.chunks()[0].clone();
+
+        map.set_tile(1, 1, 3);
+        let chunk = &map.chunks()[0];
+        assert_eq!(chunk.positions(), before.positions());
+        assert_eq!(chunk.indices(), before.indices());
+        assert_eq!(chunk.uvs()[0], [0.5, 1.0]);
+
+        map.set_tile(1, 1, EMPTY_TILE);
+        let chunk = &map.chunks()[0];
+        assert_eq!(chunk.positions().len(), 8);
+        assert!(chunk.positions()[..4].iter().all(|p| *p == [1.0, 2.0]));
+        map.set_tile(1, 1, 0);
+        assert_eq!(map.chunks()[0].positions().len(), 8);
+    }
+
+    #[test]
+    fn culls_chunks_outside_the_view() {
+        let mut map = TileMap::new(40, 40, 1.0, [1, 1]);
+        assert_eq!(map.chunks().len(), 9);
+        let view = Aabb::new([1.0, 1.0], [5.0, 5.0]);
+        assert_eq!(map.visible_chunks(&view).count(), 1);
+        let wide = Aabb::new([0.0, 0.0], [20.0, 10.0]);
+        assert_eq!(map.visible_chunks(&wide).count(), 4);
+        map.offset = [100.0, 0.0];
+        assert_eq!(map.visible_chunks(&view).count(), 0);
+    }
+
+    #[test]
+    fn malformed_csv_reports_position() {
+        assert_eq!(
+            TileMap::from_csv("0,1\n2,x\n", 1.0, [2, 2]),
+            Err(ParseTileMapError::InvalidIndex { line: 2, column: 2 })
+        );
+        assert_eq!(
+            TileMap::from_csv("0,1\n2\n", 1.0, [2, 2]),
+            Err(ParseTileMapError::RaggedRow { line: 2 })
+        );
+        assert_eq!(
+            TileMap::from_csv("\n", 1.0, [2, 2]),
+            Err(ParseTileMapError::Empty)
+        );
+    }
+}
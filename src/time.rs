@@ -0,0 +1,397 @@
+use std::time::{Duration, Instant};
+
+/// Delta reported for the very first frame, before there is a previous one to measure against.
+const FIRST_FRAME_DELTA: Duration = Duration::from_nanos(16_666_667);
+
+/// Largest delta a single frame may report. Anything longer (a window drag, a breakpoint) is
+/// clamped so simulations don't jump.
+const MAX_DELTA: Duration = Duration::from_millis(100);
+
+/// Frame clock tracking the clamped delta between frames and the total elapsed time.
+#[derive(Debug, Clone)]
+pub struct Clock {
+    last: Option<Instant>,
+    delta: Duration,
+    elapsed: f32,
+    max_delta: Duration,
+    paused: bool,
+}
+
+impl Clock {
+    pub fn new() -> Self {
+        Self {
+            last: None,
+            delta: Duration::ZERO,
+            elapsed: 0.0,
+            max_delta: MAX_DELTA,
+            paused: false,
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Freezes the clock. While paused every frame reports a zero delta and elapsed time stops.
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    /// Sets the largest delta a single frame may report.
+    pub fn set_max_delta(&mut self, max_delta: Duration) {
+        self.max_delta = max_delta;
+    }
+
+    /// Advances the clock to now. Call once at the start of every frame.
+    pub fn tick(&mut self) {
+        self.tick_at(Instant::now());
+    }
+
+    /// Advances the clock to `now`.
+    pub fn tick_at(&mut self, now: Instant) {
+        let delta = match self.last {
+            Some(last) => now.saturating_duration_since(last).min(self.max_delta),
+            None => FIRST_FRAME_DELTA.min(self.max_delta),
+        };
+        self.last = Some(now);
+        self.delta = if self.paused { Duration::ZERO } else { delta };
+        self.elapsed += self.dt();
+    }
+
+    /// Time since the previous frame, clamped, or zero while paused. This is what
+    /// [`Timers::advance`](crate::timer::Timers::advance) expects.
+    pub fn delta(&self) -> Duration {
+        self.delta
+    }
+
+    /// Seconds since the previous frame, clamped, or zero while paused.
+    pub fn dt(&self) -> f32 {
+        self.delta.as_secs_f32()
+    }
+
+    /// Sum of all clamped deltas so far, in seconds.
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+}
+
+impl Default for Clock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Default fixed update rate, in Hz.
+const DEFAULT_FIXED_HZ: f32 = 60.0;
+
+/// Most fixed steps a single frame may run before the backlog is dropped.
+const DEFAULT_MAX_STEPS: u32 = 8;
+
+/// Accumulator driving a fixed-rate update independently of the frame rate.
+///
+/// Feed it each frame's delta with [`FixedTimestep::advance`], run the fixed update as many
+/// times as it returns, then use [`FixedTimestep::alpha`] to interpolate between the last two
+/// fixed states when rendering.
+#[derive(Debug, Clone)]
+pub struct FixedTimestep {
+    step: Duration,
+    accumulator: Duration,
+    max_steps: u32,
+}
+
+impl FixedTimestep {
+    pub fn new(step: Duration) -> Self {
+        assert!(!step.is_zero(), "fixed timestep must be non-zero");
+        Self {
+            step,
+            accumulator: Duration::ZERO,
+            max_steps: DEFAULT_MAX_STEPS,
+        }
+    }
+
+    /// A timestep running `hz` fixed updates per second.
+    pub fn from_hz(hz: f32) -> Self {
+        assert!(hz > 0.0, "fixed update rate must be positive");
+        // Truncate to whole nanoseconds so that e.g. 0.1s at 60 Hz is exactly 6 steps rather
+        // than 5 and a remainder.
+        Self::new(Duration::from_nanos((1e9 / hz as f64) as u64))
+    }
+
+    pub fn step(&self) -> Duration {
+        self.step
+    }
+
+    /// The fixed delta in seconds, to pass to each fixed update.
+    pub fn dt(&self) -> f32 {
+        self.step.as_secs_f32()
+    }
+
+    /// Caps how many fixed steps one frame may run. A long stall beyond this is dropped instead
+    /// of spiralling into ever more catch-up ticks.
+    pub fn set_max_steps(&mut self, max_steps: u32) {
+        self.max_steps = max_steps.max(1);
+    }
+
+    /// Adds a frame's delta and returns how many fixed steps should run this frame.
+    pub fn advance(&mut self, dt: Duration) -> u32 {
+        self.accumulator += dt;
+        let mut steps = 0;
+        while self.accumulator >= self.step && steps < self.max_steps {
+            self.accumulator -= self.step;
+            steps += 1;
+        }
+        if steps == self.max_steps {
+            // Whatever is left over would only pile up, keep just the partial step.
+            let rem = self.accumulator.as_nanos() % self.step.as_nanos();
+            self.accumulator = Duration::from_nanos(rem as u64);
+        }
+        steps
+    }
+
+    /// How far between the last fixed step and the next one the current frame sits, in 0..1.
+    pub fn alpha(&self) -> f32 {
+        self.accumulator.as_secs_f32() / self.step.as_secs_f32()
+    }
+}
+
+impl Default for FixedTimestep {
+    fn default() -> Self {
+        Self::from_hz(DEFAULT_FIXED_HZ)
+    }
+}
+
+/// Number of frames [`FrameStats`] averages over by default.
+const DEFAULT_STATS_WINDOW: usize = 120;
+
+/// Rolling frame-time statistics over the last N frames.
+///
+/// Storage is allocated once up front, recording a frame never allocates.
+#[derive(Debug, Clone)]
+pub struct FrameStats {
+    samples: Vec<f32>,
+    next: usize,
+    len: usize,
+}
+
+impl FrameStats {
+    /// Creates statistics over a rolling window of `window` frames.
+    pub fn new(window: usize) -> Self {
+        let window = window.max(1);
+        Self {
+            samples: vec![0.0; window],
+            next: 0,
+            len: 0,
+        }
+    }
+
+    /// Number of frames the statistics cover.
+    pub fn window(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Changes how many frames the statistics cover. Recorded frames are discarded.
+    pub fn set_window(&mut self, window: usize) {
+        *self = Self::new(window);
+    }
+
+    /// Records the duration of one whole frame (update, render and present), in seconds.
+    pub fn record(&mut self, frame_time: f32) {
+        self.samples[self.next] = frame_time;
+        self.next = (self.next + 1) % self.samples.len();
+        self.len = (self.len + 1).min(self.samples.len());
+    }
+
+    /// Average frame time over the window in seconds, 0 before any frame was recorded.
+    pub fn average(&self) -> f32 {
+        if self.len == 0 {
+            return 0.0;
+        }
+        self.recorded().iter().sum::<f32>() / self.len as f32
+    }
+
+    /// Frames per second derived from the average frame time.
+    pub fn fps(&self) -> f32 {
+        let average = self.average();
+        if average > 0.0 {
+            1.0 / average
+        } else {
+            0.0
+        }
+    }
+
+    /// 95th percentile frame time over the window in seconds.
+    ///
+    /// This sorts a copy of the window, so call it when displaying stats rather than every frame.
+    pub fn percentile_95(&self) -> f32 {
+        if self.len == 0 {
+            return 0.0;
+        }
+        let mut sorted = self.recorded().to_vec();
+        sorted.sort_by(f32::total_cmp);
+        let rank = (self.len as f32 * 0.95).ceil() as usize;
+        sorted[rank.clamp(1, self.len) - 1]
+    }
+
+    fn recorded(&self) -> &[f32] {
+        &self.samples[..self.len]
+    }
+}
+
+impl Default for FrameStats {
+    fn default() -> Self {
+        Self::new(DEFAULT_STATS_WINDOW)
+    }
+}
+
+/// How close to a frame deadline [`FrameLimiter::wait`] stops sleeping and starts spinning.
+const SPIN_MARGIN: Duration = Duration::from_millis(2);
+
+/// Caps the frame rate by sleeping away whatever is left of each frame's budget.
+#[derive(Debug, Clone, Default)]
+pub struct FrameLimiter {
+    target_fps: Option<u32>,
+}
+
+impl FrameLimiter {
+    /// A limiter capping at `target_fps`. `None` or zero disables limiting.
+    pub fn new(target_fps: Option<u32>) -> Self {
+        Self {
+            target_fps: target_fps.filter(|fps| *fps > 0),
+        }
+    }
+
+    pub fn target_fps(&self) -> Option<u32> {
+        self.target_fps
+    }
+
+    /// Sets the frame rate to cap at. `None` or zero disables limiting.
+    pub fn set_target_fps(&mut self, target_fps: Option<u32>) {
+        self.target_fps = target_fps.filter(|fps| *fps > 0);
+    }
+
+    /// Time per frame at the target rate, if limiting is enabled.
+    pub fn budget(&self) -> Option<Duration> {
+        self.target_fps.map(|fps| Duration::from_secs(1) / fps)
+    }
+
+    /// How long to sleep after a frame that already took `spent` (update and render).
+    pub fn remaining(&self, spent: Duration) -> Duration {
+        self.budget()
+            .map_or(Duration::ZERO, |budget| budget.saturating_sub(spent))
+    }
+
+    /// Waits out the rest of the frame that started at `frame_start`.
+    ///
+    /// OS sleeps routinely overshoot by a millisecond or more, so this sleeps coarsely until
+    /// 2ms before the deadline and spins for the last stretch.
+    pub fn wait(&self, frame_start: Instant) {
+        let Some(budget) = self.budget() else {
+            return;
+        };
+        let deadline = frame_start + budget;
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining > SPIN_MARGIN {
+            std::thread::sleep(remaining - SPIN_MARGIN);
+        }
+        while Instant::now() < deadline {
+            std::hint::spin_loop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clock_reports_clamped_delta_and_holds_while_paused() {
+        let start = Instant::now();
+        let mut clock = Clock::new();
+        clock.tick_at(start);
+        assert_eq!(clock.delta(), FIRST_FRAME_DELTA);
+        clock.tick_at(start + Duration::from_millis(5));
+        assert_eq!(clock.delta(), Duration::from_millis(5));
+        clock.tick_at(start + Duration::from_secs(2));
+        assert_eq!(clock.delta(), MAX_DELTA);
+        clock.set_paused(true);
+        clock.tick_at(start + Duration::from_secs(3));
+        assert_eq!(clock.delta(), Duration::ZERO);
+        assert_eq!(clock.dt(), 0.0);
+    }
+
+    #[test]
+    fn frame_stats_average_over_partial_window() {
+        let mut stats = FrameStats::new(4);
+        assert_eq!(stats.average(), 0.0);
+        assert_eq!(stats.fps(), 0.0);
+        stats.record(0.01);
+        stats.record(0.03);
+        assert!((stats.average() - 0.02).abs() < 1e-6);
+        assert!((stats.fps() - 50.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn frame_stats_drop_oldest_frames() {
+        let mut stats = FrameStats::new(2);
+        stats.record(1.0);
+        stats.record(0.02);
+        stats.record(0.04);
+        assert!((stats.average() - 0.03).abs() < 1e-6);
+    }
+
+    #[test]
+    fn frame_stats_percentile_picks_slow_frames() {
+        let mut stats = FrameStats::new(20);
+        for _ in 0..18 {
+            stats.record(0.01);
+        }
+        stats.record(0.05);
+        stats.record(0.1);
+        assert_eq!(stats.percentile_95(), 0.05);
+    }
+
+    #[test]
+    fn frame_stats_fps_over_resized_window() {
+        let mut stats = FrameStats::default();
+        stats.set_window(3);
+        assert_eq!(stats.window(), 3);
+        for dt in [0.015, 0.017, 0.016, 0.018] {
+            stats.record(dt);
+        }
+        // Only the last three deltas count: average 17ms.
+        assert!((stats.fps() - 1.0 / 0.017).abs() < 0.01);
+    }
+
+    #[test]
+    fn fixed_timestep_runs_six_steps_per_tenth_second_at_60hz() {
+        let mut fixed = FixedTimestep::from_hz(60.0);
+        assert_eq!(fixed.advance(Duration::from_millis(100)), 6);
+        assert!(fixed.alpha() < 1e-3);
+    }
+
+    #[test]
+    fn fixed_timestep_drops_backlog_beyond_max_steps() {
+        let mut fixed = FixedTimestep::new(Duration::from_millis(10));
+        fixed.set_max_steps(3);
+        assert_eq!(fixed.advance(Duration::from_millis(55)), 3);
+        assert!((fixed.alpha() - 0.5).abs() < 1e-3);
+    }
+
+    #[test]
+    fn limiter_sleeps_rest_of_60fps_budget() {
+        let limiter = FrameLimiter::new(Some(60));
+        assert_eq!(
+            limiter.remaining(Duration::from_millis(5)),
+            Duration::from_nanos(11_666_666)
+        );
+        assert_eq!(limiter.remaining(Duration::from_millis(20)), Duration::ZERO);
+    }
+
+    #[test]
+    fn limiter_treats_zero_fps_as_unlimited() {
+        let limiter = FrameLimiter::new(Some(0));
+        assert_eq!(limiter.target_fps(), None);
+        assert_eq!(limiter.remaining(Duration::from_millis(5)), Duration::ZERO);
+        limiter.wait(Instant::now());
+    }
+}
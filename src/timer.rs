@@ -0,0 +1,207 @@
+use std::time::Duration;
+
+/// Identifies a scheduled callback so it can be cancelled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TimerHandle(u64);
+
+/// What a repeating timer does when a single advance covers several of its periods.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CatchUp {
+    /// Fire once and skip the missed periods.
+    #[default]
+    Once,
+    /// Fire once for every period that elapsed.
+    All,
+}
+
+struct Timer<C> {
+    handle: TimerHandle,
+    remaining: Duration,
+    period: Option<Duration>,
+    callback: Box<dyn FnMut(&mut C)>,
+}
+
+/// Delayed and repeating callbacks driven by the frame clock.
+///
+/// Callbacks receive the context passed to [`Timers::advance`]. Timers due in the same advance
+/// fire in the order they were registered, not by how overdue they are.
+pub struct Timers<C> {
+    timers: Vec<Timer<C>>,
+    next_id: u64,
+    catch_up: CatchUp,
+}
+
+impl<C> Timers<C> {
+    pub fn new() -> Self {
+        Self {
+            timers: Vec::new(),
+            next_id: 0,
+            catch_up: CatchUp::Once,
+        }
+    }
+
+    pub fn set_catch_up(&mut self, catch_up: CatchUp) {
+        self.catch_up = catch_up;
+    }
+
+    /// Runs `callback` once, `delay` from now.
+    pub fn after(
+        &mut self,
+        delay: Duration,
+        callback: impl FnMut(&mut C) + 'static,
+    ) -> TimerHandle {
+        self.schedule(delay, None, Box::new(callback))
+    }
+
+    /// Runs `callback` every `period`, first `period` from now. A zero period fires on every
+    /// advance that moves time forward.
+    pub fn every(
+        &mut self,
+        period: Duration,
+        callback: impl FnMut(&mut C) + 'static,
+    ) -> TimerHandle {
+        self.schedule(period, Some(period), Box::new(callback))
+    }
+
+    /// Stops a timer. Returns whether it was still scheduled.
+    pub fn cancel(&mut self, handle: TimerHandle) -> bool {
+        let before = self.timers.len();
+        self.timers.retain(|t| t.handle != handle);
+        self.timers.len() != before
+    }
+
+    pub fn is_scheduled(&self, handle: TimerHandle) -> bool {
+        self.timers.iter().any(|t| t.handle == handle)
+    }
+
+    pub fn len(&self) -> usize {
+        self.timers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.timers.is_empty()
+    }
+
+    /// Moves time forward by `dt` and fires everything that came due.
+    ///
+    /// A zero `dt`, as the clock reports while paused, holds every timer, including ones with a
+    /// zero delay or period.
+    pub fn advance(&mut self, dt: Duration, ctx: &mut C) {
+        if dt.is_zero() {
+            return;
+        }
+        let catch_up = self.catch_up;
+        self.timers.retain_mut(|timer| {
+            if dt < timer.remaining {
+                timer.remaining -= dt;
+                return true;
+            }
+            let overshoot = dt - timer.remaining;
+            let Some(period) = timer.period else {
+                (timer.callback)(ctx);
+                return false;
+            };
+            let (fires, remaining) = if period.is_zero() {
+                (1, Duration::ZERO)
+            } else {
+                let missed = overshoot.as_nanos() / period.as_nanos();
+                let into = overshoot.as_nanos() % period.as_nanos();
+                (1 + missed, period - Duration::from_nanos(into as u64))
+            };
+            let fires = match catch_up {
+                CatchUp::Once => 1,
+                CatchUp::All => fires,
+            };
+            for _ in 0..fires {
+                (timer.callback)(ctx);
+            }
+            timer.remaining = remaining;
+            true
+        });
+    }
+
+    fn schedule(
+        &mut self,
+        delay: Duration,
+        period: Option<Duration>,
+        callback: Box<dyn FnMut(&mut C)>,
+    ) -> TimerHandle {
+        let handle = TimerHandle(self.next_id);
+        self.next_id += 1;
+        self.timers.push(Timer {
+            handle,
+            remaining: delay,
+            period,
+            callback,
+        });
+        handle
+    }
+}
+
+impl<C> Default for Timers<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MS: Duration = Duration::from_millis(1);
+
+    #[test]
+    fn due_timers_fire_in_registration_order() {
+        let mut timers = Timers::<Vec<&str>>::new();
+        timers.after(30 * MS, |log| log.push("first"));
+        timers.every(10 * MS, |log| log.push("second"));
+        timers.after(MS, |log| log.push("third"));
+        let mut log = Vec::new();
+        timers.advance(50 * MS, &mut log);
+        assert_eq!(log, ["first", "second", "third"]);
+        assert_eq!(timers.len(), 1);
+    }
+
+    #[test]
+    fn spike_fires_once_or_catches_up() {
+        for (catch_up, expected) in [(CatchUp::Once, 1), (CatchUp::All, 3)] {
+            let mut timers = Timers::<u32>::new();
+            timers.set_catch_up(catch_up);
+            timers.every(100 * MS, |count| *count += 1);
+            let mut count = 0;
+            timers.advance(350 * MS, &mut count);
+            assert_eq!(count, expected, "{catch_up:?}");
+            // The period keeps its phase: 50ms into the next one.
+            timers.advance(49 * MS, &mut count);
+            assert_eq!(count, expected, "{catch_up:?}");
+            timers.advance(MS, &mut count);
+            assert_eq!(count, expected + 1, "{catch_up:?}");
+        }
+    }
+
+    #[test]
+    fn zero_dt_holds_timers() {
+        let mut timers = Timers::<u32>::new();
+        timers.after(Duration::ZERO, |count| *count += 1);
+        timers.every(Duration::ZERO, |count| *count += 10);
+        let mut count = 0;
+        timers.advance(Duration::ZERO, &mut count);
+        assert_eq!(count, 0);
+        timers.advance(MS, &mut count);
+        assert_eq!(count, 11);
+        timers.advance(Duration::ZERO, &mut count);
+        assert_eq!(count, 11);
+    }
+
+    #[test]
+    fn cancelled_timer_never_fires() {
+        let mut timers = Timers::<u32>::new();
+        let handle = timers.after(10 * MS, |count| *count += 1);
+        assert!(timers.cancel(handle));
+        assert!(!timers.cancel(handle));
+        assert!(!timers.is_scheduled(handle));
+        let mut count = 0;
+        timers.advance(20 * MS, &mut count);
+        assert_eq!(count, 0);
+    }
+}
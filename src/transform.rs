@@ -0,0 +1,226 @@
+/// A 3x3 row-major matrix for 2D affine transforms, applied to column vectors `[x, y, 1]`.
+pub type Mat3 = [[f32; 3]; 3];
+
+pub const IDENTITY: Mat3 = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+
+/// Returns `a * b`, the transform applying `b` first and then `a`.
+pub fn mul(a: &Mat3, b: &Mat3) -> Mat3 {
+    let mut out = [[0.0; 3]; 3];
+    for (row, out_row) in out.iter_mut().enumerate() {
+        for (col, value) in out_row.iter_mut().enumerate() {
+            *value = (0..3).map(|k| a[row][k] * b[k][col]).sum();
+        }
+    }
+    out
+}
+
+/// Applies `m` to a point.
+pub fn transform_point(m: &Mat3, p: [f32; 2]) -> [f32; 2] {
+    [
+        m[0][0] * p[0] + m[0][1] * p[1] + m[0][2],
+        m[1][0] * p[0] + m[1][1] * p[1] + m[1][2],
+    ]
+}
+
+/// Translation, rotation (radians, counter-clockwise) and scale, applied scale first.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform2D {
+    pub translation: [f32; 2],
+    pub rotation: f32,
+    pub scale: [f32; 2],
+}
+
+impl Transform2D {
+    pub const IDENTITY: Transform2D = Transform2D {
+        translation: [0.0, 0.0],
+        rotation: 0.0,
+        scale: [1.0, 1.0],
+    };
+
+    pub fn from_translation(translation: [f32; 2]) -> Self {
+        Self {
+            translation,
+            ..Self::IDENTITY
+        }
+    }
+
+    pub fn to_matrix(&self) -> Mat3 {
+        let (sin, cos) = self.rotation.sin_cos();
+        let [sx, sy] = self.scale;
+        let [tx, ty] = self.translation;
+        [
+            [cos * sx, -sin * sy, tx],
+            [sin * sx, cos * sy, ty],
+            [0.0, 0.0, 1.0],
+        ]
+    }
+}
+
+impl Default for Transform2D {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+/// Index of a transform inside a [`Hierarchy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TransformId(usize);
+
+/// Why a parent could not be assigned in a [`Hierarchy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HierarchyError {
+    /// The parent is the child itself or one of its descendants.
+    Cycle,
+}
+
+impl std::fmt::Display for HierarchyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Cycle => write!(f, "parenting would create a cycle"),
+        }
+    }
+}
+
+impl std::error::Error for HierarchyError {}
+
+/// A flat set of transforms with optional parents, resolved into world matrices once per frame.
+#[derive(Debug, Clone, Default)]
+pub struct Hierarchy {
+    locals: Vec<Transform2D>,
+    parents: Vec<Option<TransformId>>,
+    worlds: Vec<Mat3>,
+}
+
+impl Hierarchy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, transform: Transform2D) -> TransformId {
+        self.locals.push(transform);
+        self.parents.push(None);
+        self.worlds.push(IDENTITY);
+        TransformId(self.locals.len() - 1)
+    }
+
+    /// Inserts a transform positioned relative to `parent`.
+    pub fn insert_child(&mut self, parent: TransformId, transform: Transform2D) -> TransformId {
+        let id = self.insert(transform);
+        self.parents[id.0] = Some(parent);
+        id
+    }
+
+    pub fn local(&self, id: TransformId) -> &Transform2D {
+        &self.locals[id.0]
+    }
+
+    pub fn local_mut(&mut self, id: TransformId) -> &mut Transform2D {
+        &mut self.locals[id.0]
+    }
+
+    pub fn parent(&self, id: TransformId) -> Option<TransformId> {
+        self.parents[id.0]
+    }
+
+    /// Direct children of `id`, in insertion order.
+    pub fn children(&self, id: TransformId) -> impl Iterator<Item = TransformId> + '_ {
+        self.parents
+            .iter()
+            .enumerate()
+            .filter(move |(_, parent)| **parent == Some(id))
+            .map(|(child, _)| TransformId(child))
+    }
+
+    /// Attaches `child` to `parent`, or detaches it with `None`. Rejects cycles.
+    pub fn set_parent(
+        &mut self,
+        child: TransformId,
+        parent: Option<TransformId>,
+    ) -> Result<(), HierarchyError> {
+        let mut ancestor = parent;
+        while let Some(id) = ancestor {
+            if id == child {
+                return Err(HierarchyError::Cycle);
+            }
+            ancestor = self.parents[id.0];
+        }
+        self.parents[child.0] = parent;
+        Ok(())
+    }
+
+    /// Recomputes every world matrix from the local transforms and parents.
+    pub fn resolve(&mut self) {
+        let mut resolved = vec![false; self.locals.len()];
+        for id in 0..self.locals.len() {
+            self.resolve_one(id, &mut resolved);
+        }
+    }
+
+    fn resolve_one(&mut self, id: usize, resolved: &mut [bool]) {
+        if resolved[id] {
+            return;
+        }
+        let local = self.locals[id].to_matrix();
+        self.worlds[id] = match self.parents[id] {
+            Some(parent) => {
+                self.resolve_one(parent.0, resolved);
+                mul(&self.worlds[parent.0], &local)
+            }
+            None => local,
+        };
+        resolved[id] = true;
+    }
+
+    /// World matrix as of the last [`Hierarchy::resolve`].
+    pub fn world(&self, id: TransformId) -> &Mat3 {
+        &self.worlds[id.0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn child_position_is_relative_to_parent() {
+        let mut hierarchy = Hierarchy::new();
+        let parent = hierarchy.insert(Transform2D::from_translation([5.0, 5.0]));
+        let child = hierarchy.insert_child(parent, Transform2D::from_translation([1.0, 0.0]));
+        hierarchy.resolve();
+        assert_eq!(
+            transform_point(hierarchy.world(parent), [0.0, 0.0]),
+            [5.0, 5.0]
+        );
+        assert_eq!(
+            transform_point(hierarchy.world(child), [0.0, 0.0]),
+            [6.0, 5.0]
+        );
+        assert_eq!(hierarchy.children(parent).collect::<Vec<_>>(), vec![child]);
+
+        hierarchy.local_mut(parent).translation = [0.0, 0.0];
+        hierarchy.resolve();
+        assert_eq!(
+            transform_point(hierarchy.world(child), [0.0, 0.0]),
+            [1.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn parenting_cycles_are_rejected() {
+        let mut hierarchy = Hierarchy::new();
+        let root = hierarchy.insert(Transform2D::IDENTITY);
+        let child = hierarchy.insert_child(root, Transform2D::IDENTITY);
+        let grandchild = hierarchy.insert_child(child, Transform2D::IDENTITY);
+        assert_eq!(
+            hierarchy.set_parent(root, Some(grandchild)),
+            Err(HierarchyError::Cycle)
+        );
+        assert_eq!(
+            hierarchy.set_parent(child, Some(child)),
+            Err(HierarchyError::Cycle)
+        );
+        assert_eq!(hierarchy.parent(root), None);
+        assert_eq!(hierarchy.set_parent(grandchild, Some(root)), Ok(()));
+        assert_eq!(hierarchy.parent(grandchild), Some(root));
+    }
+}
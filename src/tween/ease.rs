@@ -0,0 +1,120 @@
+use std::f32::consts::PI;
+
+/// Easing curves mapping linear progress in 0..1 to eased progress.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Ease {
+    #[default]
+    Linear,
+    InQuad,
+    OutQuad,
+    InOutQuad,
+    InCubic,
+    OutCubic,
+    InOutCubic,
+    InElastic,
+    OutElastic,
+    InBounce,
+    OutBounce,
+}
+
+impl Ease {
+    /// Eases `t`, clamped to 0..1. Every curve maps 0 to 0 and 1 to 1, elastic curves
+    /// overshoot in between.
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Ease::Linear => t,
+            Ease::InQuad => t * t,
+            Ease::OutQuad => 1.0 - (1.0 - t) * (1.0 - t),
+            Ease::InOutQuad => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(2) / 2.0
+                }
+            }
+            Ease::InCubic => t * t * t,
+            Ease::OutCubic => 1.0 - (1.0 - t).powi(3),
+            Ease::InOutCubic => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+            Ease::InElastic => {
+                if t == 0.0 || t == 1.0 {
+                    t
+                } else {
+                    -(2f32.powf(10.0 * t - 10.0)) * ((t * 10.0 - 10.75) * (2.0 * PI / 3.0)).sin()
+                }
+            }
+            Ease::OutElastic => {
+                if t == 0.0 || t == 1.0 {
+                    t
+                } else {
+                    2f32.powf(-10.0 * t) * ((t * 10.0 - 0.75) * (2.0 * PI / 3.0)).sin() + 1.0
+                }
+            }
+            Ease::InBounce => 1.0 - out_bounce(1.0 - t),
+            Ease::OutBounce => out_bounce(t),
+        }
+    }
+}
+
+fn out_bounce(t: f32) -> f32 {
+    const N: f32 = 7.5625;
+    const D: f32 = 2.75;
+    if t < 1.0 / D {
+        N * t * t
+    } else if t < 2.0 / D {
+        let t = t - 1.5 / D;
+        N * t * t + 0.75
+    } else if t < 2.5 / D {
+        let t = t - 2.25 / D;
+        N * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / D;
+        N * t * t + 0.984375
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [Ease; 11] = [
+        Ease::Linear,
+        Ease::InQuad,
+        Ease::OutQuad,
+        Ease::InOutQuad,
+        Ease::InCubic,
+        Ease::OutCubic,
+        Ease::InOutCubic,
+        Ease::InElastic,
+        Ease::OutElastic,
+        Ease::InBounce,
+        Ease::OutBounce,
+    ];
+
+    #[test]
+    fn every_curve_maps_endpoints_to_themselves() {
+        for ease in ALL {
+            assert!(ease.apply(0.0).abs() < 1e-5, "{ease:?} at 0");
+            assert!((ease.apply(1.0) - 1.0).abs() < 1e-5, "{ease:?} at 1");
+            assert_eq!(ease.apply(-0.5), ease.apply(0.0), "{ease:?} below 0");
+            assert_eq!(ease.apply(1.5), ease.apply(1.0), "{ease:?} above 1");
+        }
+    }
+
+    #[test]
+    fn bounce_is_continuous_at_breakpoints() {
+        const D: f32 = 2.75;
+        for breakpoint in [1.0 / D, 2.0 / D, 2.5 / D] {
+            let before = out_bounce(breakpoint - 1e-4);
+            let at = out_bounce(breakpoint);
+            assert!((before - at).abs() < 1e-3, "jump at {breakpoint}");
+            assert!((at - 1.0).abs() < 1e-5, "bounce at {breakpoint} is {at}");
+        }
+    }
+}
@@ -0,0 +1,345 @@
+mod ease;
+
+pub use ease::Ease;
+
+use crate::color::Color;
+use crate::entity::{Entities, Entity};
+
+/// Values a [`Tween`] can interpolate.
+pub trait Lerp: Copy {
+    fn lerp(self, to: Self, t: f32) -> Self;
+}
+
+impl Lerp for f32 {
+    fn lerp(self, to: Self, t: f32) -> Self {
+        self + (to - self) * t
+    }
+}
+
+impl Lerp for [f32; 2] {
+    fn lerp(self, to: Self, t: f32) -> Self {
+        [self[0].lerp(to[0], t), self[1].lerp(to[1], t)]
+    }
+}
+
+impl Lerp for Color {
+    fn lerp(self, to: Self, t: f32) -> Self {
+        Color::lerp(self, to, t)
+    }
+}
+
+/// Animates a value from one end to the other over a duration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tween<T> {
+    pub from: T,
+    pub to: T,
+    /// Seconds the tween takes.
+    pub duration: f32,
+    pub ease: Ease,
+    pub looping: bool,
+    elapsed: f32,
+}
+
+impl<T: Lerp> Tween<T> {
+    pub fn new(from: T, to: T, duration: f32) -> Self {
+        Self {
+            from,
+            to,
+            duration,
+            ease: Ease::Linear,
+            looping: false,
+            elapsed: 0.0,
+        }
+    }
+
+    pub fn easing(mut self, ease: Ease) -> Self {
+        self.ease = ease;
+        self
+    }
+
+    /// Makes the tween start over each time it completes.
+    pub fn looped(mut self) -> Self {
+        self.looping = true;
+        self
+    }
+
+    /// Advances by `dt` seconds and returns the new value.
+    pub fn tick(&mut self, dt: f32) -> T {
+        self.elapsed += dt;
+        if self.looping && self.duration > 0.0 {
+            self.elapsed %= self.duration;
+        } else {
+            self.elapsed = self.elapsed.min(self.duration);
+        }
+        self.value()
+    }
+
+    pub fn value(&self) -> T {
+        self.from.lerp(self.to, self.ease.apply(self.progress()))
+    }
+
+    /// Linear progress through the tween, in 0..1.
+    pub fn progress(&self) -> f32 {
+        if self.duration > 0.0 {
+            self.elapsed / self.duration
+        } else {
+            1.0
+        }
+    }
+
+    /// Whether a non-looping tween has reached its end.
+    pub fn is_finished(&self) -> bool {
+        !self.looping && self.elapsed >= self.duration
+    }
+}
+
+/// Tweens played one after another, each starting where the clock left the previous one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sequence<T> {
+    steps: Vec<Tween<T>>,
+    current: usize,
+    pub looping: bool,
+}
+
+impl<T: Lerp> Sequence<T> {
+    pub fn new(first: Tween<T>) -> Self {
+        Self {
+            steps: vec![first],
+            current: 0,
+            looping: false,
+        }
+    }
+
+    /// Appends a tween to play after the ones already added.
+    pub fn then(mut self, next: Tween<T>) -> Self {
+        self.steps.push(next);
+        self
+    }
+
+    pub fn looped(mut self) -> Self {
+        self.looping = true;
+        self
+    }
+
+    /// Advances by `dt` seconds, carrying leftover time into the following steps, and returns
+    /// the new value.
+    pub fn tick(&mut self, mut dt: f32) -> T {
+        loop {
+            let last = self.current + 1 == self.steps.len();
+            let step = &mut self.steps[self.current];
+            let remaining = step.duration - step.elapsed;
+            if dt < remaining || (last && !self.looping) {
+                return step.tick(dt);
+            }
+            step.tick(remaining);
+            dt -= remaining;
+            if !last {
+                self.current += 1;
+            } else if self.steps.iter().all(|s| s.duration <= 0.0) {
+                // Nothing would ever consume the leftover time.
+                return self.value();
+            } else {
+                self.current = 0;
+                for step in &mut self.steps {
+                    step.elapsed = 0.0;
+                }
+            }
+        }
+    }
+
+    pub fn value(&self) -> T {
+        self.steps[self.current].value()
+    }
+
+    /// Whether a non-looping sequence has played every step.
+    pub fn is_finished(&self) -> bool {
+        !self.looping
+            && self.current + 1 == self.steps.len()
+            && self.steps[self.current].is_finished()
+    }
+}
+
+impl<T: Lerp> From<Tween<T>> for Sequence<T> {
+    fn from(tween: Tween<T>) -> Self {
+        Sequence::new(tween)
+    }
+}
+
+/// Identifies a running tween so it can be cancelled or polled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TweenHandle(u64);
+
+/// Writes a tween's current value into its entity.
+type Apply<T, E> = Box<dyn FnMut(&mut E, T)>;
+
+struct Running<T, E> {
+    handle: TweenHandle,
+    entity: Entity,
+    sequence: Sequence<T>,
+    apply: Apply<T, E>,
+}
+
+/// Tweens driving properties of entities, advanced from the update loop.
+///
+/// Each tween writes its value into its entity through an `apply` callback. A tween whose
+/// entity has been despawned is dropped on the next advance instead of touching the slot.
+pub struct Tweens<T, E> {
+    running: Vec<Running<T, E>>,
+    finished: Vec<TweenHandle>,
+    next_id: u64,
+}
+
+impl<T: Lerp, E> Tweens<T, E> {
+    pub fn new() -> Self {
+        Self {
+            running: Vec::new(),
+            finished: Vec::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Starts animating `entity`, passing each new value to `apply`.
+    pub fn add(
+        &mut self,
+        entity: Entity,
+        tween: impl Into<Sequence<T>>,
+        apply: impl FnMut(&mut E, T) + 'static,
+    ) -> TweenHandle {
+        let handle = TweenHandle(self.next_id);
+        self.next_id += 1;
+        self.running.push(Running {
+            handle,
+            entity,
+            sequence: tween.into(),
+            apply: Box::new(apply),
+        });
+        handle
+    }
+
+    /// Stops a tween where it is. Returns whether it was still running.
+    pub fn cancel(&mut self, handle: TweenHandle) -> bool {
+        let before = self.running.len();
+        self.running.retain(|r| r.handle != handle);
+        self.running.len() != before
+    }
+
+    /// Whether a tween is still running. False once it finished, was cancelled or its entity
+    /// was despawned.
+    pub fn is_running(&self, handle: TweenHandle) -> bool {
+        self.running.iter().any(|r| r.handle == handle)
+    }
+
+    pub fn len(&self) -> usize {
+        self.running.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.running.is_empty()
+    }
+
+    /// Moves every tween forward by `dt` seconds and applies the new values. Tweens that
+    /// complete are reported by [`Tweens::finished`] until the next advance.
+    pub fn advance(&mut self, dt: f32, entities: &mut Entities<E>) {
+        self.finished.clear();
+        let finished = &mut self.finished;
+        self.running.retain_mut(|running| {
+            let Ok(target) = entities.get_mut(running.entity) else {
+                return false;
+            };
+            (running.apply)(target, running.sequence.tick(dt));
+            if running.sequence.is_finished() {
+                finished.push(running.handle);
+                return false;
+            }
+            true
+        });
+    }
+
+    /// Tweens that completed during the last [`Tweens::advance`], in the order they were added.
+    pub fn finished(&self) -> &[TweenHandle] {
+        &self.finished
+    }
+}
+
+impl<T: Lerp, E> Default for Tweens<T, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn looped_tween_wraps() {
+        let mut tween = Tween::new(0.0, 10.0, 1.0).looped();
+        assert_eq!(tween.tick(1.25), 2.5);
+        assert!(!tween.is_finished());
+    }
+
+    #[test]
+    fn sequence_carries_leftover_time_into_next_step() {
+        let mut sequence = Sequence::new(Tween::new(0.0, 1.0, 1.0)).then(Tween::new(1.0, 3.0, 1.0));
+        assert_eq!(sequence.tick(1.5), 2.0);
+        assert!(!sequence.is_finished());
+        assert_eq!(sequence.tick(1.0), 3.0);
+        assert!(sequence.is_finished());
+    }
+
+    #[test]
+    fn looped_sequence_restarts_with_leftover_time() {
+        let mut sequence = Sequence::new(Tween::new(0.0, 1.0, 1.0))
+            .then(Tween::new(1.0, 3.0, 1.0))
+            .looped();
+        assert_eq!(sequence.tick(2.5), 0.5);
+    }
+
+    #[test]
+    fn looped_sequence_of_zero_length_steps_terminates() {
+        let mut sequence = Sequence::new(Tween::new(0.0, 1.0, 0.0))
+            .then(Tween::new(1.0, 2.0, 0.0))
+            .looped();
+        assert_eq!(sequence.tick(1.0), 2.0);
+    }
+
+    #[test]
+    fn tweens_apply_values_and_report_completion() {
+        let mut entities = Entities::new();
+        let entity = entities.spawn([0.0f32, 0.0]);
+        let mut tweens = Tweens::new();
+        let handle = tweens.add(
+            entity,
+            Tween::new([0.0, 0.0], [4.0, 2.0], 1.0),
+            |position: &mut [f32; 2], value| *position = value,
+        );
+        tweens.advance(0.5, &mut entities);
+        assert_eq!(entities.get(entity), Ok(&[2.0, 1.0]));
+        assert!(tweens.finished().is_empty());
+        tweens.advance(0.5, &mut entities);
+        assert_eq!(entities.get(entity), Ok(&[4.0, 2.0]));
+        assert_eq!(tweens.finished(), &[handle]);
+        assert!(!tweens.is_running(handle));
+        tweens.advance(0.5, &mut entities);
+        assert!(tweens.finished().is_empty());
+    }
+
+    #[test]
+    fn despawning_mid_tween_cancels_it() {
+        let mut entities = Entities::new();
+        let entity = entities.spawn(0.0f32);
+        let mut tweens = Tweens::new();
+        let handle = tweens.add(
+            entity,
+            Tween::new(0.0, 10.0, 1.0),
+            |size: &mut f32, value| *size = value,
+        );
+        tweens.advance(0.5, &mut entities);
+        assert_eq!(entities.despawn(entity), Ok(5.0));
+        let reused = entities.spawn(-1.0);
+        tweens.advance(0.5, &mut entities);
+        assert!(!tweens.is_running(handle));
+        assert!(tweens.finished().is_empty());
+        assert_eq!(entities.get(reused), Ok(&-1.0));
+    }
+}